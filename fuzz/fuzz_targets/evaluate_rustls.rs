@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use limbo_harness_support::models::Testcase;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tc) = serde_json::from_slice::<Testcase>(data) {
+        rust_rustls_harness::evaluate_testcase(&tc);
+    }
+});
@@ -0,0 +1,21 @@
+#!/usr/bin/env python3
+
+"""
+Seeds each fuzz target's corpus with the testcases in `limbo.json`,
+one testcase per file.
+"""
+
+import hashlib
+import json
+from pathlib import Path
+
+_FUZZ = Path(__file__).parent
+_TARGETS = [p.stem for p in (_FUZZ / "fuzz_targets").glob("*.rs")]
+
+limbo = json.loads((_FUZZ.parent / "limbo.json").read_text())
+for target in _TARGETS:
+    corpus = _FUZZ / "corpus" / target
+    corpus.mkdir(parents=True, exist_ok=True)
+    for testcase in limbo["testcases"]:
+        data = json.dumps(testcase).encode()
+        (corpus / hashlib.sha1(data).hexdigest()).write_bytes(data)
@@ -0,0 +1,77 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::models::{ActualResult, Testcase, TestcaseResult};
+
+/// Evaluation times for a single testcase, over repeated runs.
+pub struct Timing {
+    id: String,
+    times: Vec<Duration>,
+    actual_result: ActualResult,
+}
+
+impl Timing {
+    /// Evaluates `testcase` `iterations` times, recording how long each
+    /// evaluation takes. The recorded result is the last run's.
+    pub fn measure(
+        testcase: &Testcase,
+        iterations: usize,
+        evaluate: impl Fn(&Testcase) -> TestcaseResult,
+    ) -> Self {
+        assert!(iterations > 0, "at least one iteration is required");
+
+        let mut times = Vec::with_capacity(iterations);
+        let mut actual_result = ActualResult::Skipped;
+        for _ in 0..iterations {
+            let start = Instant::now();
+            actual_result = evaluate(testcase).actual_result;
+            times.push(start.elapsed());
+        }
+        times.sort();
+
+        Timing {
+            id: testcase.id.to_string(),
+            times,
+            actual_result,
+        }
+    }
+
+    fn median(&self) -> Duration {
+        let mid = self.times.len() / 2;
+        if self.times.len().is_multiple_of(2) {
+            (self.times[mid - 1] + self.times[mid]) / 2
+        } else {
+            self.times[mid]
+        }
+    }
+}
+
+fn ms(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+/// Renders `timings` as a CSV, with one row per testcase:
+///
+/// ```csv
+/// id,min_ms,median_ms,max_ms,actual_result
+/// rfc5280::nc::permitted-dns-match,0.112,0.118,0.154,SUCCESS
+/// ```
+pub fn render_csv(timings: &[Timing]) -> String {
+    let mut out = String::new();
+    writeln!(out, "id,min_ms,median_ms,max_ms,actual_result").unwrap();
+    for timing in timings {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            timing.id,
+            ms(timing.times[0]),
+            ms(timing.median()),
+            ms(timing.times[timing.times.len() - 1]),
+            timing.actual_result
+        )
+        .unwrap();
+    }
+    out
+}
@@ -1,7 +1,173 @@
+use std::{collections::HashMap, ffi::OsStr, fs, fs::File, io::BufReader, path::Path};
+
 use models::Limbo;
 
+pub mod benchmark;
 pub mod models;
+pub mod report;
+pub mod runner;
+pub mod skips;
+pub mod unsupported;
+
+/// The Limbo test suite, embedded at build time.
+#[cfg(feature = "embedded-suite")]
+pub static EMBEDDED_LIMBO: &[u8] = include_bytes!("../../../limbo.json");
+
+/// Parses the Limbo test suite from a JSON string.
+pub fn load_limbo_from_str(json: &str) -> Result<Limbo, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// Parses the Limbo test suite from JSON bytes.
+pub fn load_limbo_from_bytes(json: &[u8]) -> Result<Limbo, serde_json::Error> {
+    serde_json::from_slice(json)
+}
+
+/// Loads the Limbo test suite from the JSON file at `path`.
+pub fn load_limbo_from_path(path: &Path) -> Limbo {
+    let file = File::open(path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+/// Loads the Limbo test suite from the `*.json` files in `dir`, each of
+/// which is a suite containing a subset of the testcases, and merges them.
+///
+/// Panics if `dir` has no such files, if any of them isn't a valid suite, or
+/// if a testcase ID appears more than once. Since the schema only admits a
+/// single `version`, valid fragments always agree on it.
+pub fn load_limbo_from_directory(dir: &Path) -> Limbo {
+    let mut paths = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("{}: {e}", dir.display()))
+        .map(|entry| {
+            entry
+                .unwrap_or_else(|e| panic!("{}: {e}", dir.display()))
+                .path()
+        })
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    paths.sort();
 
+    let mut merged: Option<Limbo> = None;
+    let mut sources = HashMap::new();
+    for path in &paths {
+        let fragment = load_limbo_from_path(path);
+        for tc in &fragment.testcases {
+            if let Some(first) = sources.insert(tc.id.to_string(), path) {
+                panic!(
+                    "duplicate testcase ID {} in {} and {}",
+                    tc.id.as_str(),
+                    first.display(),
+                    path.display()
+                );
+            }
+        }
+        if let Some(limbo) = &mut merged {
+            limbo.testcases.extend(fragment.testcases);
+        } else {
+            merged = Some(fragment);
+        }
+    }
+    merged.unwrap_or_else(|| panic!("no *.json files in {}", dir.display()))
+}
+
+/// Loads the Limbo test suite from `path` if given, or from stdin otherwise.
+/// If `path` is a directory, the suite is merged from the fragments in it
+/// (see [`load_limbo_from_directory`]).
+pub fn load_limbo_from(path: Option<&Path>) -> Limbo {
+    match path {
+        Some(path) if path.is_dir() => load_limbo_from_directory(path),
+        Some(path) => load_limbo_from_path(path),
+        None => serde_json::from_reader(std::io::stdin()).unwrap(),
+    }
+}
+
+/// Loads the Limbo test suite from the path in `LIMBO_PATH` if set,
+/// or from stdin otherwise. If `LIMBO_PATH` is a directory, the suite is
+/// merged from the fragments in it (see [`load_limbo_from_directory`]).
 pub fn load_limbo() -> Limbo {
-    serde_json::from_reader(std::io::stdin()).unwrap()
+    load_limbo_from_var(std::env::var_os("LIMBO_PATH").as_deref())
+}
+
+/// Loads the Limbo test suite as [`load_limbo`] does, given the value of
+/// `LIMBO_PATH`.
+fn load_limbo_from_var(limbo_path: Option<&OsStr>) -> Limbo {
+    load_limbo_from(limbo_path.map(Path::new))
+}
+
+/// Loads the Limbo test suite that was embedded at build time.
+#[cfg(feature = "embedded-suite")]
+pub fn load_embedded_limbo() -> Limbo {
+    load_limbo_from_bytes(EMBEDDED_LIMBO).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+    }
+
+    fn ids(limbo: &Limbo) -> Vec<&str> {
+        limbo.testcases.iter().map(|tc| tc.id.as_str()).collect()
+    }
+
+    #[test]
+    fn load_limbo_from_fixture_path() {
+        let limbo = load_limbo_from(Some(&fixtures().join("limbo.json")));
+
+        assert_eq!(ids(&limbo), ["pathlen::max-chain-depth-0"]);
+    }
+
+    #[test]
+    fn load_limbo_from_limbo_path() {
+        let limbo_path = fixtures().join("limbo.json");
+        let limbo = load_limbo_from_var(Some(limbo_path.as_os_str()));
+
+        assert_eq!(ids(&limbo), ["pathlen::max-chain-depth-0"]);
+    }
+
+    #[test]
+    fn load_limbo_from_fixture_directory() {
+        let limbo = load_limbo_from(Some(&fixtures()));
+
+        assert_eq!(ids(&limbo), ["pathlen::max-chain-depth-0"]);
+    }
+
+    #[test]
+    fn load_limbo_from_fragment_directory() {
+        let limbo = load_limbo_from(Some(&fixtures().join("fragments")));
+
+        assert_eq!(limbo.testcases.len(), 3);
+        assert_eq!(
+            ids(&limbo),
+            [
+                "pathlen::max-chain-depth-0",
+                "pathlen::max-chain-depth-0-a",
+                "pathlen::max-chain-depth-0-b",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate testcase ID pathlen::max-chain-depth-0")]
+    fn load_limbo_from_directory_with_duplicate_ids() {
+        load_limbo_from(Some(&fixtures().join("duplicate-ids")));
+    }
+
+    // `b.json` has a version that the schema doesn't admit.
+    #[test]
+    #[should_panic(expected = "invalid-fragment/b.json")]
+    fn load_limbo_from_directory_with_invalid_fragment() {
+        load_limbo_from(Some(&fixtures().join("invalid-fragment")));
+    }
+
+    #[test]
+    #[should_panic(expected = "does-not-exist.json")]
+    fn load_limbo_from_missing_path() {
+        load_limbo_from(Some(&fixtures().join("does-not-exist.json")));
+    }
 }
@@ -1,9 +1,12 @@
+// typify's expansion implements `ToString` directly.
+#![allow(clippy::to_string_trait_impl)]
+
 use serde::{Deserialize, Serialize};
 use typify::import_types;
 
 import_types!(schema = "../../limbo-schema.json");
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum ActualResult {
     Success,
@@ -11,19 +14,128 @@ pub enum ActualResult {
     Skipped,
 }
 
-#[derive(Serialize)]
+/// Why a testcase was skipped.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", content = "detail", rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// The testcase requires something the implementation (or its API)
+    /// doesn't support.
+    UnsupportedFeature(String),
+    /// The implementation is known to disagree with the testcase.
+    KnownBug(String),
+    /// The testcase exercises a lint-style check, rather than validation.
+    LinterCheck,
+    /// Evaluating the testcase took too long.
+    Timeout,
+    /// The testcase itself is known to be wrong.
+    BrokenTestcase,
+    /// The testcase wasn't evaluated, e.g. because of `--fail-fast`.
+    NotEvaluated,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::UnsupportedFeature(detail) | SkipReason::KnownBug(detail) => {
+                write!(f, "{detail}")
+            }
+            SkipReason::LinterCheck => write!(f, "linter check"),
+            SkipReason::Timeout => write!(f, "timed out"),
+            SkipReason::BrokenTestcase => write!(f, "broken testcase"),
+            SkipReason::NotEvaluated => write!(f, "not evaluated"),
+        }
+    }
+}
+
+/// A machine-readable category for a result's `context`, so that results
+/// can be filtered without parsing the context string.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResultContextCode {
+    /// The testcase requires a feature that the implementation doesn't
+    /// support.
+    UnsupportedFeature,
+    /// The testcase's profile or validation kind isn't supported.
+    UnsupportedProfile,
+    /// The testcase constrains signature algorithms, which isn't supported.
+    UnsupportedSignatureAlgorithm,
+    /// The testcase constrains key usages, which isn't supported.
+    UnsupportedKeyUsage,
+    /// The testcase's expected peer name is missing or of an unsupported
+    /// kind.
+    UnsupportedPeerName,
+    /// An input, such as a certificate or the validation time, couldn't be
+    /// parsed or converted.
+    ParseError,
+    /// No valid path was found from the peer certificate to a trust anchor.
+    PathBuildingFailed,
+    /// The peer certificate isn't valid for the expected peer name, or the
+    /// expected peer name itself is invalid.
+    PeerNameCheckFailed,
+    /// The implementation is known to disagree with the testcase.
+    KnownBug,
+    /// The testcase exercises a lint-style check, rather than validation.
+    LinterCheck,
+    /// Evaluating the testcase took too long.
+    Timeout,
+    /// The testcase itself is known to be wrong.
+    BrokenTestcase,
+    /// The testcase wasn't evaluated, e.g. because of `--fail-fast`.
+    NotEvaluated,
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct TestcaseResult {
     pub id: String,
     pub actual_result: ActualResult,
     pub context: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_code: Option<ResultContextCode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<SkipReason>,
+    /// The testcase's description; only included in verbose runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl std::fmt::Display for ActualResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActualResult::Success => write!(f, "SUCCESS"),
+            ActualResult::Failure => write!(f, "FAILURE"),
+            ActualResult::Skipped => write!(f, "SKIPPED"),
+        }
+    }
+}
+
+impl ActualResult {
+    /// Returns whether this result matches `expected`. Skipped results
+    /// match nothing.
+    pub fn matches(&self, expected: &ExpectedResult) -> bool {
+        matches!(
+            (expected, self),
+            (ExpectedResult::Success, ActualResult::Success)
+                | (ExpectedResult::Failure, ActualResult::Failure)
+        )
+    }
 }
 
 impl TestcaseResult {
-    pub fn fail(tc: &Testcase, reason: &str) -> Self {
+    /// Returns whether this result is neither skipped nor the result that
+    /// `tc` expects.
+    pub fn is_unexpected(&self, tc: &Testcase) -> bool {
+        !matches!(self.actual_result, ActualResult::Skipped)
+            && !self.actual_result.matches(&tc.expected_result)
+    }
+
+    pub fn fail(tc: &Testcase, code: ResultContextCode, reason: &str) -> Self {
         TestcaseResult {
             id: tc.id.to_string(),
             actual_result: ActualResult::Failure,
             context: Some(reason.into()),
+            context_code: Some(code),
+            skip_reason: None,
+            description: None,
         }
     }
 
@@ -32,21 +144,61 @@ impl TestcaseResult {
             id: tc.id.to_string(),
             actual_result: ActualResult::Success,
             context: None,
+            context_code: None,
+            skip_reason: None,
+            description: None,
         }
     }
 
-    pub fn skip(tc: &Testcase, reason: &str) -> Self {
+    pub fn skip(tc: &Testcase, code: ResultContextCode, reason: SkipReason) -> Self {
         TestcaseResult {
             id: tc.id.to_string(),
             actual_result: ActualResult::Skipped,
-            context: Some(reason.into()),
+            context: Some(reason.to_string()),
+            context_code: Some(code),
+            skip_reason: Some(reason),
+            description: None,
+        }
+    }
+}
+
+/// Information about the environment that produced a `LimboResult`.
+#[derive(Serialize, Deserialize)]
+pub struct HarnessMetadata {
+    pub harness_version: String,
+    pub rust_version: String,
+    pub os: String,
+    pub timestamp_utc: String,
+    pub limbo_suite_version: u32,
+}
+
+impl HarnessMetadata {
+    pub fn new(harness_version: &str, limbo: &Limbo) -> Self {
+        HarnessMetadata {
+            harness_version: harness_version.into(),
+            rust_version: option_env!("RUSTUP_TOOLCHAIN").unwrap_or("unknown").into(),
+            os: std::env::consts::OS.into(),
+            timestamp_utc: chrono::Utc::now().to_rfc3339(),
+            limbo_suite_version: limbo.suite_version(),
         }
     }
 }
 
-#[derive(Serialize)]
+impl Limbo {
+    /// Returns the suite's `version`.
+    pub fn suite_version(&self) -> u32 {
+        u32::try_from(i64::from(self.version.clone())).expect("suite version out of range")
+    }
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct LimboResult {
     pub version: u8,
     pub harness: String,
+    /// The `version` of the `Limbo` suite that these results are for.
+    pub suite_version: u32,
     pub results: Vec<TestcaseResult>,
+    // Optional, since not every harness produces it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HarnessMetadata>,
 }
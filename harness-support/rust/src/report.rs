@@ -0,0 +1,83 @@
+use std::{collections::BTreeMap, collections::HashMap, fmt::Write};
+
+use crate::models::{ActualResult, ExpectedResult, Limbo, TestcaseResult};
+
+#[derive(Default)]
+struct Counts {
+    total: usize,
+    pass: usize,
+    fail: usize,
+    skip: usize,
+}
+
+impl Counts {
+    fn add(&mut self, expected: &ExpectedResult, actual: ActualResult) {
+        self.total += 1;
+        match actual {
+            ActualResult::Skipped => self.skip += 1,
+            _ if actual.matches(expected) => self.pass += 1,
+            _ => self.fail += 1,
+        }
+    }
+}
+
+/// Renders a table of results grouped by testcase namespace, e.g. `rfc5280`,
+/// and by sub-namespace, e.g. `rfc5280::nc`.
+///
+/// A result "passes" if it matches the testcase's expected result.
+pub fn namespace_report(limbo: &Limbo, results: &[TestcaseResult]) -> String {
+    let expected = limbo
+        .testcases
+        .iter()
+        .map(|tc| (tc.id.as_str(), &tc.expected_result))
+        .collect::<HashMap<_, _>>();
+
+    // Namespaces map to their own counts, and the counts of each of their
+    // sub-namespaces.
+    let mut namespaces: BTreeMap<&str, (Counts, BTreeMap<&str, Counts>)> = BTreeMap::new();
+    for result in results {
+        let Some(expected) = expected.get(result.id.as_str()) else {
+            continue;
+        };
+
+        let segments = result.id.split("::").collect::<Vec<_>>();
+        let (counts, subnamespaces) = namespaces.entry(segments[0]).or_default();
+        counts.add(expected, result.actual_result);
+
+        // Only IDs like `a::b::c` have a sub-namespace; in `a::b`, `b` is
+        // the testcase's own name.
+        if segments.len() > 2 {
+            let end = segments[0].len() + 2 + segments[1].len();
+            subnamespaces
+                .entry(&result.id[..end])
+                .or_default()
+                .add(expected, result.actual_result);
+        }
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:<40} {:>6} {:>6} {:>6} {:>6}",
+        "namespace", "total", "pass", "fail", "skip"
+    )
+    .unwrap();
+
+    let mut row = |name: &str, counts: &Counts| {
+        writeln!(
+            out,
+            "{:<40} {:>6} {:>6} {:>6} {:>6}",
+            name, counts.total, counts.pass, counts.fail, counts.skip
+        )
+        .unwrap();
+    };
+
+    for (namespace, (counts, subnamespaces)) in &namespaces {
+        row(namespace, counts);
+        for (subnamespace, counts) in subnamespaces {
+            row(&format!("  {subnamespace}"), counts);
+        }
+    }
+
+    out
+}
@@ -0,0 +1,615 @@
+use std::{
+    fs::File,
+    io::{stdout, BufWriter, Write},
+    panic,
+    path::{Path, PathBuf},
+    process::exit,
+    sync::{
+        mpsc::{channel, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    benchmark::{render_csv, Timing},
+    load_limbo,
+    models::{
+        HarnessMetadata, Limbo, LimboResult, ResultContextCode, SkipReason, Testcase,
+        TestcaseResult,
+    },
+    report::namespace_report,
+    skips::{Skip, SkipConfig, RATIONALE_PLACEHOLDER},
+};
+
+/// Command-line options shared by all Rust harnesses.
+#[derive(Default)]
+struct Options {
+    /// Load the test suite embedded at build time, rather than from
+    /// `LIMBO_PATH` or stdin.
+    embedded: bool,
+    /// Print a per-namespace summary of the results to stderr.
+    report_by_namespace: bool,
+    /// Stop at the first unexpected result.
+    fail_fast: bool,
+    /// Skip the testcases listed in this TOML file.
+    skip_config: Option<PathBuf>,
+    /// Write the testcases with unexpected results to this TOML file.
+    update_skips: Option<PathBuf>,
+    /// Print each result and its testcase's description to stderr, and
+    /// include the description in the results.
+    verbose: bool,
+    /// Only evaluate the testcase with this ID.
+    filter_id: Option<String>,
+    /// Evaluate each testcase this many times, and write its evaluation
+    /// times as CSV instead of writing results.
+    benchmark: Option<usize>,
+    /// Write the results to this file rather than stdout. `-` means stdout.
+    output_file: Option<PathBuf>,
+    /// Skip testcases that take longer than this to evaluate.
+    timeout: Option<Duration>,
+}
+
+impl Options {
+    fn parse() -> Self {
+        Self::parse_from(std::env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            exit(2);
+        })
+    }
+
+    fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--embedded" => options.embedded = true,
+                "--report-by-namespace" => options.report_by_namespace = true,
+                "--fail-fast" => options.fail_fast = true,
+                "--verbose" => options.verbose = true,
+                "--skip-config" => options.skip_config = Some(Self::path(&arg, args.next())?),
+                "--update-skips" => options.update_skips = Some(Self::path(&arg, args.next())?),
+                "--filter-id" => options.filter_id = Some(Self::value(&arg, args.next())?),
+                "--benchmark" => options.benchmark = Some(Self::count(&arg, args.next())?),
+                "--output-file" => options.output_file = Some(Self::path(&arg, args.next())?),
+                "--timeout" => {
+                    let secs = Self::count(&arg, args.next())?;
+                    options.timeout = Some(Duration::from_secs(secs as u64));
+                }
+                _ => return Err(format!("unknown argument: {arg}")),
+            }
+        }
+        Ok(options)
+    }
+
+    fn path(flag: &str, value: Option<String>) -> Result<PathBuf, String> {
+        value
+            .map(PathBuf::from)
+            .ok_or_else(|| format!("{flag} requires a file argument"))
+    }
+
+    fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+        value.ok_or_else(|| format!("{flag} requires an argument"))
+    }
+
+    fn count(flag: &str, value: Option<String>) -> Result<usize, String> {
+        match value.as_deref().map(str::parse) {
+            Some(Ok(count)) if count > 0 => Ok(count),
+            _ => Err(format!("{flag} requires a positive integer argument")),
+        }
+    }
+
+    /// Opens the destination for the harness's output: the `--output-file`
+    /// if one was given, or stdout otherwise.
+    fn output(&self) -> Box<dyn Write> {
+        match self.output_file.as_deref() {
+            Some(path) if path != Path::new("-") => {
+                let file = File::create(path)
+                    .unwrap_or_else(|e| panic!("couldn't create {}: {e}", path.display()));
+                Box::new(BufWriter::new(file))
+            }
+            _ => Box::new(stdout().lock()),
+        }
+    }
+}
+
+/// Evaluates `testcase` on a separate thread, skipping it if evaluation
+/// doesn't finish within `timeout`. A panic during evaluation is propagated
+/// to the caller. On timeout, the evaluating thread is left to finish (or
+/// not) in the background.
+fn evaluate_with_timeout<F>(
+    evaluate: &Arc<F>,
+    testcase: &Testcase,
+    timeout: Duration,
+) -> TestcaseResult
+where
+    F: Fn(&Testcase) -> TestcaseResult + Send + Sync + 'static,
+{
+    let (tx, rx) = channel();
+    let evaluate = Arc::clone(evaluate);
+    let owned = testcase.clone();
+    let handle = thread::Builder::new()
+        .name(testcase.id.to_string())
+        .spawn(move || tx.send(evaluate(&owned)))
+        .unwrap_or_else(|e| panic!("{}: {e}", testcase.id.as_str()));
+
+    match rx.recv_timeout(timeout) {
+        Ok(result) => {
+            let _ = handle.join();
+            result
+        }
+        Err(RecvTimeoutError::Timeout) => {
+            TestcaseResult::skip(testcase, ResultContextCode::Timeout, SkipReason::Timeout)
+        }
+        // The sender is only dropped without sending if evaluation panicked.
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(_) => unreachable!("evaluation finished without a result"),
+        },
+    }
+}
+
+/// The results of evaluating every testcase in a suite.
+struct Evaluation {
+    results: Vec<TestcaseResult>,
+    /// The testcases to write to `--update-skips`.
+    new_skips: SkipConfig,
+    /// The testcase that `--fail-fast` stopped at, if any.
+    unexpected: Option<String>,
+}
+
+/// Evaluates every testcase in `limbo`, honoring `--fail-fast`,
+/// `--skip-config` and `--timeout`.
+fn evaluate_all<F>(limbo: &Limbo, options: &Options, evaluate: &Arc<F>) -> Evaluation
+where
+    F: Fn(&Testcase) -> TestcaseResult + Send + Sync + 'static,
+{
+    let skip_config = options
+        .skip_config
+        .as_deref()
+        .map(SkipConfig::load)
+        .unwrap_or_default();
+    let configured_skips = skip_config.by_id();
+    let mut new_skips = SkipConfig::default();
+
+    let mut results = vec![];
+    let mut unexpected = None;
+    let mut testcases = limbo.testcases.iter();
+    for testcase in testcases.by_ref() {
+        if let Some(rationale) = configured_skips.get(testcase.id.as_str()) {
+            new_skips.skips.push(Skip {
+                id: testcase.id.to_string(),
+                rationale: rationale.to_string(),
+            });
+            results.push(TestcaseResult::skip(
+                testcase,
+                ResultContextCode::KnownBug,
+                SkipReason::KnownBug(rationale.to_string()),
+            ));
+            continue;
+        }
+
+        let result = match options.timeout {
+            Some(timeout) => evaluate_with_timeout(evaluate, testcase, timeout),
+            None => evaluate(testcase),
+        };
+        let is_unexpected = result.is_unexpected(testcase);
+        results.push(result);
+
+        if is_unexpected {
+            new_skips.skips.push(Skip {
+                id: testcase.id.to_string(),
+                rationale: RATIONALE_PLACEHOLDER.into(),
+            });
+        }
+
+        if options.fail_fast && is_unexpected {
+            unexpected = Some(testcase.id.to_string());
+            break;
+        }
+    }
+
+    for testcase in testcases {
+        results.push(TestcaseResult::skip(
+            testcase,
+            ResultContextCode::NotEvaluated,
+            SkipReason::NotEvaluated,
+        ));
+    }
+
+    Evaluation {
+        results,
+        new_skips,
+        unexpected,
+    }
+}
+
+/// Removes every testcase from `limbo` except the one with ID `id`.
+fn filter_testcases(limbo: &mut Limbo, id: &str) -> Result<(), String> {
+    limbo.testcases.retain(|tc| tc.id.as_str() == id);
+    if limbo.testcases.is_empty() {
+        return Err(format!("no testcase with ID: {id}"));
+    }
+    Ok(())
+}
+
+/// Evaluates each testcase in `limbo` `iterations` times, and renders
+/// their evaluation times as CSV.
+fn benchmark(
+    limbo: &Limbo,
+    iterations: usize,
+    evaluate: impl Fn(&Testcase) -> TestcaseResult + Copy,
+) -> String {
+    let timings = limbo
+        .testcases
+        .iter()
+        .map(|tc| Timing::measure(tc, iterations, evaluate))
+        .collect::<Vec<_>>();
+    render_csv(&timings)
+}
+
+#[cfg(feature = "embedded-suite")]
+fn load_embedded() -> Limbo {
+    crate::load_embedded_limbo()
+}
+
+#[cfg(not(feature = "embedded-suite"))]
+fn load_embedded() -> Limbo {
+    panic!("--embedded requires the embedded-suite feature");
+}
+
+/// Runs `evaluate` over every testcase in the suite and writes the
+/// resulting `LimboResult` to stdout, identifying it as `harness` at
+/// `harness_version`.
+///
+/// With `--fail-fast`, evaluation stops at the first result that doesn't
+/// match its testcase's expectation: the remaining testcases are recorded as
+/// skipped, so that the output is still a complete `LimboResult`, and the
+/// process exits with status 1.
+///
+/// With `--skip-config FILE`, the testcases listed in `FILE` are skipped
+/// without being evaluated. With `--update-skips FILE`, every testcase with
+/// an unexpected result is written to `FILE` in the same format, along with
+/// any testcases that were skipped by `--skip-config`.
+///
+/// With `--filter-id ID`, only the testcase with ID `ID` is evaluated.
+///
+/// With `--benchmark N`, each testcase is evaluated `N` times, and a CSV of
+/// each testcase's minimum, median and maximum evaluation times is written
+/// to stdout instead of a `LimboResult`. Benchmarking ignores every other
+/// option besides `--embedded`, `--filter-id` and `--output-file`.
+///
+/// With `--output-file PATH`, the results (or benchmark CSV) are written to
+/// `PATH` rather than stdout. `--output-file -` writes to stdout.
+///
+/// With `--timeout SECS`, a testcase that takes longer than `SECS` seconds to
+/// evaluate is recorded as skipped, with a `timeout` context code. This
+/// doesn't stop the evaluation: it keeps running on a background thread
+/// until it finishes or the process exits, so it can still slow down the
+/// testcases after it. `--benchmark` ignores `--timeout`.
+pub fn run(
+    harness: &str,
+    harness_version: &str,
+    evaluate: impl Fn(&Testcase) -> TestcaseResult + Send + Sync + 'static,
+) {
+    let options = Options::parse();
+    let evaluate = Arc::new(evaluate);
+
+    let mut limbo = if options.embedded {
+        load_embedded()
+    } else {
+        load_limbo()
+    };
+    let metadata = HarnessMetadata::new(harness_version, &limbo);
+
+    if let Some(id) = &options.filter_id {
+        if let Err(e) = filter_testcases(&mut limbo, id) {
+            eprintln!("{e}");
+            exit(2);
+        }
+    }
+
+    if let Some(iterations) = options.benchmark {
+        let mut output = options.output();
+        output
+            .write_all(benchmark(&limbo, iterations, &*evaluate).as_bytes())
+            .unwrap();
+        output.flush().unwrap();
+        return;
+    }
+
+    let Evaluation {
+        mut results,
+        new_skips,
+        unexpected,
+    } = evaluate_all(&limbo, &options, &evaluate);
+
+    if options.verbose {
+        for (testcase, result) in limbo.testcases.iter().zip(results.iter_mut()) {
+            eprintln!(
+                "{}: {} (expected {})",
+                testcase.id.as_str(),
+                result.actual_result,
+                testcase.expected_result.to_string()
+            );
+            for line in testcase.description.lines() {
+                if line.is_empty() {
+                    eprintln!();
+                } else {
+                    eprintln!("    {line}");
+                }
+            }
+            result.description = Some(testcase.description.clone());
+        }
+    }
+
+    if let Some(path) = &options.update_skips {
+        new_skips.write(path);
+    }
+
+    if options.report_by_namespace {
+        eprint!("{}", namespace_report(&limbo, &results));
+    }
+
+    let result = LimboResult {
+        version: 1,
+        harness: harness.into(),
+        suite_version: limbo.suite_version(),
+        results,
+        metadata: Some(metadata),
+    };
+
+    let mut output = options.output();
+    serde_json::to_writer_pretty(&mut output, &result).unwrap();
+    output.flush().unwrap();
+
+    if let Some(id) = unexpected {
+        eprintln!("unexpected result: {id}");
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ActualResult;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    /// Returns the fixture suite, with its one testcase (which expects
+    /// success) repeated under each of `ids`.
+    fn suite(ids: &[&str]) -> Limbo {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/limbo.json");
+        let mut limbo = crate::load_limbo_from_path(&path);
+        let testcase = limbo.testcases.pop().unwrap();
+        limbo.testcases = ids
+            .iter()
+            .map(|id| Testcase {
+                id: id.parse().unwrap(),
+                ..testcase.clone()
+            })
+            .collect();
+        limbo
+    }
+
+    /// Fails the testcases whose IDs end in `fail`, and succeeds otherwise.
+    fn stub(testcase: &Testcase) -> TestcaseResult {
+        if testcase.id.as_str().ends_with("fail") {
+            TestcaseResult::fail(testcase, ResultContextCode::PathBuildingFailed, "stub")
+        } else {
+            TestcaseResult::success(testcase)
+        }
+    }
+
+    fn actual_results(evaluation: &Evaluation) -> Vec<ActualResult> {
+        evaluation
+            .results
+            .iter()
+            .map(|result| result.actual_result)
+            .collect()
+    }
+
+    #[test]
+    fn parse_options() {
+        let options = Options::parse_from(args(&[
+            "--fail-fast",
+            "--verbose",
+            "--skip-config",
+            "skips.toml",
+            "--timeout",
+            "5",
+        ]))
+        .unwrap();
+
+        assert!(options.fail_fast);
+        assert!(options.verbose);
+        assert!(!options.embedded);
+        assert_eq!(options.skip_config, Some(PathBuf::from("skips.toml")));
+        assert_eq!(options.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(options.benchmark, None);
+    }
+
+    #[test]
+    fn parse_options_with_stdout_output_file() {
+        let options = Options::parse_from(args(&["--output-file", "-"])).unwrap();
+
+        assert_eq!(options.output_file, Some(PathBuf::from("-")));
+    }
+
+    #[test]
+    #[should_panic(expected = "couldn't create does-not-exist/results.json")]
+    fn output_to_unwritable_path() {
+        let options =
+            Options::parse_from(args(&["--output-file", "does-not-exist/results.json"])).unwrap();
+
+        options.output();
+    }
+
+    #[test]
+    fn parse_options_rejects_bad_arguments() {
+        let err = |a: &[&str]| Options::parse_from(args(a)).err().unwrap();
+
+        assert_eq!(err(&["--bogus"]), "unknown argument: --bogus");
+        assert_eq!(
+            err(&["--skip-config"]),
+            "--skip-config requires a file argument"
+        );
+        assert_eq!(
+            err(&["--timeout", "0"]),
+            "--timeout requires a positive integer argument"
+        );
+    }
+
+    #[test]
+    fn evaluate_all_without_fail_fast() {
+        let limbo = suite(&["stub::one", "stub::two-fail", "stub::three"]);
+        let evaluation = evaluate_all(&limbo, &Options::default(), &Arc::new(stub));
+
+        assert_eq!(
+            actual_results(&evaluation),
+            [
+                ActualResult::Success,
+                ActualResult::Failure,
+                ActualResult::Success
+            ]
+        );
+        assert_eq!(evaluation.unexpected, None);
+    }
+
+    #[test]
+    fn evaluate_all_with_fail_fast() {
+        let limbo = suite(&["stub::one", "stub::two-fail", "stub::three", "stub::four"]);
+        let options = Options {
+            fail_fast: true,
+            ..Default::default()
+        };
+        let evaluation = evaluate_all(&limbo, &options, &Arc::new(stub));
+
+        assert_eq!(
+            actual_results(&evaluation),
+            [
+                ActualResult::Success,
+                ActualResult::Failure,
+                ActualResult::Skipped,
+                ActualResult::Skipped
+            ]
+        );
+        assert!(evaluation.results[2..]
+            .iter()
+            .all(|result| result.context_code == Some(ResultContextCode::NotEvaluated)));
+        // `run` exits with status 1 when evaluation stops early.
+        assert_eq!(evaluation.unexpected.as_deref(), Some("stub::two-fail"));
+    }
+
+    #[test]
+    fn evaluate_all_with_skip_config() {
+        let path = std::env::temp_dir().join(format!("limbo-skips-{}.toml", std::process::id()));
+        SkipConfig {
+            skips: vec![Skip {
+                id: "stub::one-fail".into(),
+                rationale: "known bug".into(),
+            }],
+        }
+        .write(&path);
+
+        let limbo = suite(&["stub::one-fail", "stub::two", "stub::three-fail"]);
+        let options = Options {
+            skip_config: Some(path.clone()),
+            ..Default::default()
+        };
+        let evaluation = evaluate_all(&limbo, &options, &Arc::new(stub));
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            actual_results(&evaluation),
+            [
+                ActualResult::Skipped,
+                ActualResult::Success,
+                ActualResult::Failure
+            ]
+        );
+        assert_eq!(
+            evaluation.results[0].skip_reason,
+            Some(SkipReason::KnownBug("known bug".into()))
+        );
+
+        // `--update-skips` keeps the configured skips, and adds the new
+        // unexpected results with a placeholder rationale.
+        let new_skips = evaluation.new_skips.by_id();
+        assert_eq!(new_skips.len(), 2);
+        assert_eq!(new_skips["stub::one-fail"], "known bug");
+        assert_eq!(new_skips["stub::three-fail"], RATIONALE_PLACEHOLDER);
+    }
+
+    #[test]
+    fn filter_testcases_by_id() {
+        let mut limbo = suite(&["stub::one", "stub::two", "stub::three"]);
+        filter_testcases(&mut limbo, "stub::two").unwrap();
+
+        assert_eq!(limbo.testcases.len(), 1);
+        assert_eq!(limbo.testcases[0].id.as_str(), "stub::two");
+
+        assert_eq!(
+            filter_testcases(&mut limbo, "stub::missing"),
+            Err("no testcase with ID: stub::missing".into())
+        );
+    }
+
+    #[test]
+    fn benchmark_renders_one_row_per_testcase() {
+        let limbo = suite(&["stub::one", "stub::two-fail"]);
+        let csv = benchmark(&limbo, 3, stub);
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "id,min_ms,median_ms,max_ms,actual_result");
+        assert!(lines[1].starts_with("stub::one,"));
+        assert!(lines[1].ends_with(",SUCCESS"));
+        assert!(lines[2].starts_with("stub::two-fail,"));
+        assert!(lines[2].ends_with(",FAILURE"));
+    }
+
+    #[test]
+    fn evaluate_with_timeout_skips_slow_testcases() {
+        let limbo = suite(&["stub::slow"]);
+        let slow = |testcase: &Testcase| {
+            thread::sleep(Duration::from_secs(1));
+            TestcaseResult::success(testcase)
+        };
+        let result = evaluate_with_timeout(
+            &Arc::new(slow),
+            &limbo.testcases[0],
+            Duration::from_millis(10),
+        );
+
+        assert_eq!(result.actual_result, ActualResult::Skipped);
+        assert_eq!(result.context_code, Some(ResultContextCode::Timeout));
+    }
+
+    #[test]
+    fn evaluate_with_timeout_returns_fast_results() {
+        let limbo = suite(&["stub::fast-fail"]);
+        let result = evaluate_with_timeout(
+            &Arc::new(stub),
+            &limbo.testcases[0],
+            Duration::from_secs(10),
+        );
+
+        assert_eq!(result.actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    #[should_panic(expected = "evaluator panicked")]
+    fn evaluate_with_timeout_propagates_panics() {
+        let limbo = suite(&["stub::panic"]);
+        let panicking = |_: &Testcase| -> TestcaseResult { panic!("evaluator panicked") };
+        evaluate_with_timeout(
+            &Arc::new(panicking),
+            &limbo.testcases[0],
+            Duration::from_secs(10),
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// The placeholder rationale for newly generated skips.
+pub const RATIONALE_PLACEHOLDER: &str = "TODO: explain why this testcase is skipped";
+
+/// A single skipped testcase.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Skip {
+    pub id: String,
+    pub rationale: String,
+}
+
+/// A list of testcases to skip, loaded from (or written to) a TOML file:
+///
+/// ```toml
+/// [[skip]]
+/// id = "rfc5280::nc::permitted-dns-match"
+/// rationale = "..."
+/// ```
+#[derive(Serialize, Deserialize, Default)]
+pub struct SkipConfig {
+    #[serde(default, rename = "skip")]
+    pub skips: Vec<Skip>,
+}
+
+impl SkipConfig {
+    pub fn load(path: &Path) -> Self {
+        let contents =
+            fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        toml::from_str(&contents).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+    }
+
+    pub fn write(&self, path: &Path) {
+        fs::write(path, toml::to_string(self).unwrap())
+            .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    }
+
+    /// Returns a map of skipped testcase IDs to their rationales.
+    pub fn by_id(&self) -> HashMap<&str, &str> {
+        self.skips
+            .iter()
+            .map(|skip| (skip.id.as_str(), skip.rationale.as_str()))
+            .collect()
+    }
+}
@@ -0,0 +1,117 @@
+use crate::models::{Feature, ResultContextCode, SkipReason, Testcase, TestcaseResult};
+
+/// Features that webpki doesn't support, and why. Both the webpki and rustls
+/// harnesses skip testcases that have any of these features.
+///
+/// Pedantic features whose checks webpki does perform are deliberately left
+/// out, so that their testcases are evaluated: `strict-der` (except for
+/// `SET OF` ordering, see [`KNOWN_DIVERGENCES`]), `reject-v2` and
+/// `large-store`. Likewise `allow-underscore-in-dns`, since webpki accepts
+/// underscores in DNS names.
+pub const UNSUPPORTED: &[(Feature, &str)] = &[
+    (
+        Feature::MaxChainDepth,
+        "max-chain-depth testcases are not supported by this API",
+    ),
+    (
+        Feature::RequireEmbeddedSct,
+        "Certificate Transparency is not enforced by this API",
+    ),
+    (
+        Feature::PartialWildcardMatching,
+        "wildcards are only matched as the entire leftmost label",
+    ),
+    (
+        Feature::StrictAnchorValidity,
+        "trust anchor validity periods are not checked",
+    ),
+    (
+        Feature::StrictAnchorConstraints,
+        "trust anchor extensions are not checked",
+    ),
+    (
+        Feature::AllowExpiredLeaf,
+        "expired EE certificates are rejected",
+    ),
+    (
+        Feature::EdiPartyName,
+        "EdiPartyName constraints not supported",
+    ),
+    (
+        Feature::PostQuantum,
+        "post-quantum algorithms not yet supported",
+    ),
+    (
+        Feature::TrailingDotMatching,
+        "trailing dots in DNS SANs are significant",
+    ),
+    (
+        Feature::StrictKeyParams,
+        "RSA public exponents are not checked",
+    ),
+    (
+        Feature::StrictAsn1Integers,
+        "INTEGER encodings in public keys are not checked",
+    ),
+];
+
+/// Testcases that webpki is known to disagree with, and why. These are
+/// mostly the strict halves of `conflicts_with` pairs, where webpki
+/// behaves like the other, permissive testcase.
+pub const KNOWN_DIVERGENCES: &[(&str, &str)] = &[
+    (
+        "webpki::san::trailing-dot-peer-name",
+        "a trailing dot in the peer name is ignored",
+    ),
+    (
+        "webpki::san::underscore-label-san",
+        "underscores in DNS names are accepted",
+    ),
+    (
+        "webpki::san::leading-underscore-san",
+        "underscores in DNS names are accepted",
+    ),
+    (
+        "webpki::san::internal-underscore-san",
+        "underscores in DNS names are accepted",
+    ),
+    (
+        "rfc5280::der::ber-set-order",
+        "the DER ordering of SET OF in names is not checked",
+    ),
+    (
+        "rfc5280::uid::root-v2-unique-ids-trusted",
+        "v2 trust anchors are rejected",
+    ),
+];
+
+/// Returns a skip result for `tc` if it is one of the `divergences`.
+pub fn skip_known_divergence(
+    tc: &Testcase,
+    divergences: &[(&str, &str)],
+) -> Option<TestcaseResult> {
+    divergences
+        .iter()
+        .find(|(id, _)| tc.id.as_str() == *id)
+        .map(|(_, reason)| {
+            TestcaseResult::skip(
+                tc,
+                ResultContextCode::KnownBug,
+                SkipReason::KnownBug((*reason).into()),
+            )
+        })
+}
+
+/// Returns a skip result for `tc` if it has any of the `unsupported` features.
+pub fn skip_unsupported(tc: &Testcase, unsupported: &[(Feature, &str)]) -> Option<TestcaseResult> {
+    unsupported
+        .iter()
+        .find(|(feature, _)| tc.features.contains(feature))
+        .map(|(_, reason)| {
+            TestcaseResult::skip(
+                tc,
+                ResultContextCode::UnsupportedFeature,
+                SkipReason::UnsupportedFeature((*reason).into()),
+            )
+        })
+}
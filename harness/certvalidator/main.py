@@ -90,9 +90,12 @@ def main():
         results.append(result)
 
     print(
-        LimboResult(version=1, harness=f"certvalidator-{version}", results=results).model_dump_json(
-            indent=2
-        )
+        LimboResult(
+            version=1,
+            harness=f"certvalidator-{version}",
+            suite_version=limbo.version,
+            results=results,
+        ).model_dump_json(indent=2)
     )
 
 
@@ -36,9 +36,10 @@ type result struct {
 }
 
 type results struct {
-	Version uint     `json:"version"`
-	Harness string   `json:"harness"`
-	Results []result `json:"results"`
+	Version      uint         `json:"version"`
+	Harness      string       `json:"harness"`
+	SuiteVersion LimboVersion `json:"suite_version"`
+	Results      []result     `json:"results"`
 }
 
 func main() {
@@ -84,6 +85,7 @@ func main() {
 
 	outputResults.Version = 1
 	outputResults.Harness = fmt.Sprintf("gocryptox509-%s", runtime.Version())
+	outputResults.SuiteVersion = testcases.Version
 	resultsEncoder.Encode(outputResults)
 
 	fmt.Fprintf(os.Stderr, "done! conformant/nonconformant/skipped/total %d/%d/%d/%d.\n", conform, nonconform, skip, len(testcases.Testcases))
@@ -114,6 +116,9 @@ func evaluateTestcase(testcase Testcase) (testcaseResult, error) {
 		if feature == "max-chain-depth" {
 			return resultSkipped, fmt.Errorf("max chain depth not supported")
 		}
+		if feature == "post-quantum" {
+			return resultSkipped, fmt.Errorf("post-quantum algorithms not yet supported")
+		}
 	}
 
 	var ts time.Time
@@ -186,7 +191,9 @@ func evaluateTestcase(testcase Testcase) (testcaseResult, error) {
 		intermediates.AppendCertsFromPEM(concatPEMCerts(testcase.UntrustedIntermediates))
 
 		peerAsPEM, rest := pem.Decode([]byte(testcase.PeerCertificate))
-		if peerAsPEM == nil || peerAsPEM.Type != "CERTIFICATE" {
+		if peerAsPEM == nil {
+			return resultFailure, fmt.Errorf("unable to parse peer certificate as PEM")
+		} else if peerAsPEM.Type != "CERTIFICATE" {
 			return resultFailure, fmt.Errorf("unexpected data, expected cert: %+#v", *peerAsPEM)
 		} else if len(rest) > 0 {
 			return resultFailure, fmt.Errorf("peer certificate has %d trailing bytes", len(rest))
@@ -6,6 +6,42 @@ import "encoding/json"
 import "fmt"
 import "reflect"
 
+type CertProfile string
+
+const CertProfileCODESIGNING CertProfile = "CODE_SIGNING"
+const CertProfileGENERIC CertProfile = "GENERIC"
+const CertProfileSMIME CertProfile = "SMIME"
+const CertProfileTIMESTAMPING CertProfile = "TIME_STAMPING"
+const CertProfileTLS CertProfile = "TLS"
+
+var enumValues_CertProfile = []interface{}{
+	"TLS",
+	"SMIME",
+	"CODE_SIGNING",
+	"TIME_STAMPING",
+	"GENERIC",
+}
+
+// UnmarshalJSON implements json.Unmarshaler.
+func (j *CertProfile) UnmarshalJSON(b []byte) error {
+	var v string
+	if err := json.Unmarshal(b, &v); err != nil {
+		return err
+	}
+	var ok bool
+	for _, expected := range enumValues_CertProfile {
+		if reflect.DeepEqual(v, expected) {
+			ok = true
+			break
+		}
+	}
+	if !ok {
+		return fmt.Errorf("invalid value (expected one of %#v): %#v", enumValues_CertProfile, v)
+	}
+	*j = CertProfile(v)
+	return nil
+}
+
 type ExpectedResult string
 
 const ExpectedResultFAILURE ExpectedResult = "FAILURE"
@@ -38,18 +74,32 @@ func (j *ExpectedResult) UnmarshalJSON(b []byte) error {
 
 type Feature string
 
+const FeatureAllowExpiredLeaf Feature = "allow-expired-leaf"
+const FeatureAllowUnderscoreInDns Feature = "allow-underscore-in-dns"
 const FeatureDenialOfService Feature = "denial-of-service"
+const FeatureEdiPartyName Feature = "edi-party-name"
 const FeatureHasCertPolicies Feature = "has-cert-policies"
 const FeatureHasPolicyConstraints Feature = "has-policy-constraints"
+const FeatureLargeStore Feature = "large-store"
 const FeatureMaxChainDepth Feature = "max-chain-depth"
 const FeatureNameConstraintDn Feature = "name-constraint-dn"
 const FeatureNoCertPolicies Feature = "no-cert-policies"
+const FeaturePartialWildcardMatching Feature = "partial-wildcard-matching"
 const FeaturePedanticPublicSuffixWildcard Feature = "pedantic-public-suffix-wildcard"
 const FeaturePedanticRfc5280 Feature = "pedantic-rfc5280"
 const FeaturePedanticSerialNumber Feature = "pedantic-serial-number"
 const FeaturePedanticWebpkiEku Feature = "pedantic-webpki-eku"
 const FeaturePedanticWebpkiSubscriberKey Feature = "pedantic-webpki-subscriber-key"
+const FeaturePostQuantum Feature = "post-quantum"
+const FeatureRejectV2 Feature = "reject-v2"
+const FeatureRequireEmbeddedSct Feature = "require-embedded-sct"
 const FeatureRfc5280IncompatibleWithWebpki Feature = "rfc5280-incompatible-with-webpki"
+const FeatureStrictAnchorConstraints Feature = "strict-anchor-constraints"
+const FeatureStrictAnchorValidity Feature = "strict-anchor-validity"
+const FeatureStrictAsn1Integers Feature = "strict-asn1-integers"
+const FeatureStrictDer Feature = "strict-der"
+const FeatureStrictKeyParams Feature = "strict-key-params"
+const FeatureTrailingDotMatching Feature = "trailing-dot-matching"
 
 var enumValues_Feature = []interface{}{
 	"has-policy-constraints",
@@ -64,6 +114,20 @@ var enumValues_Feature = []interface{}{
 	"pedantic-rfc5280",
 	"rfc5280-incompatible-with-webpki",
 	"denial-of-service",
+	"strict-der",
+	"strict-key-params",
+	"strict-asn1-integers",
+	"require-embedded-sct",
+	"strict-anchor-validity",
+	"trailing-dot-matching",
+	"allow-underscore-in-dns",
+	"partial-wildcard-matching",
+	"strict-anchor-constraints",
+	"large-store",
+	"edi-party-name",
+	"allow-expired-leaf",
+	"reject-v2",
+	"post-quantum",
 }
 
 // UnmarshalJSON implements json.Unmarshaler.
@@ -267,11 +331,13 @@ type PeerKind string
 const PeerKindDNS PeerKind = "DNS"
 const PeerKindIP PeerKind = "IP"
 const PeerKindRFC822 PeerKind = "RFC822"
+const PeerKindUPN PeerKind = "UPN"
 
 var enumValues_PeerKind = []interface{}{
 	"RFC822",
 	"DNS",
 	"IP",
+	"UPN",
 }
 
 // UnmarshalJSON implements json.Unmarshaler.
@@ -456,6 +522,10 @@ type Testcase struct {
 	// The PEM-encoded private key for the peer certificate, if present
 	PeerCertificateKey interface{} `json:"peer_certificate_key,omitempty" yaml:"peer_certificate_key,omitempty" mapstructure:"peer_certificate_key,omitempty"`
 
+	// The application profile that this testcase exercises; if unset, the testcase
+	// exercises the TLS (Web PKI) profile
+	Profile interface{} `json:"profile,omitempty" yaml:"profile,omitempty" mapstructure:"profile,omitempty"`
+
 	// A list of acceptable signature algorithms to constrain against
 	SignatureAlgorithms []SignatureAlgorithm `json:"signature_algorithms" yaml:"signature_algorithms" mapstructure:"signature_algorithms"`
 
@@ -48,19 +48,18 @@ std::map<std::string, int> create_eku_map()
   return m;
 }
 
+// NOTE: Returns a null pointer if `pem` isn't a PEM-encoded certificate.
 X509_ptr pem_to_x509(const std::string &pem)
 {
   X509 *cert = nullptr;
   BIO_ptr cert_bio(BIO_new_mem_buf(pem.data(), pem.length()), BIO_free);
 
-  if (!PEM_read_bio_X509(cert_bio.get(), &cert, 0, NULL))
-  {
-    barf("failed to parse cert");
-  }
-
+  PEM_read_bio_X509(cert_bio.get(), &cert, 0, NULL);
   return X509_ptr(cert, X509_free);
 }
 
+// NOTE: Returns a null pointer if any of `certs` isn't a PEM-encoded
+// certificate.
 STACK_OF_X509_ptr x509_stack(const json &certs)
 {
   if (!certs.is_array())
@@ -68,18 +67,22 @@ STACK_OF_X509_ptr x509_stack(const json &certs)
     barf("unexpected type: expected an array of certs");
   }
 
-  auto *stack = sk_X509_new_reserve(nullptr, certs.size());
+  STACK_OF_X509_ptr stack(sk_X509_new_reserve(nullptr, certs.size()), SK_X509_free);
   for (auto &cert : certs)
   {
     auto cert_pem = cert.template get<std::string>();
     auto cert_x509 = pem_to_x509(cert_pem);
+    if (!cert_x509)
+    {
+      return STACK_OF_X509_ptr(nullptr, SK_X509_free);
+    }
     // NOTE: Our `STACK_OF_X509_ptr` takes ownership here,
     // since on destruction it uses `sk_X509_pop_free` instead
     // of `sk_X509_free`.
-    sk_X509_push(stack, cert_x509.release());
+    sk_X509_push(stack.get(), cert_x509.release());
   }
 
-  return STACK_OF_X509_ptr(stack, SK_X509_free);
+  return stack;
 }
 
 json skip(const std::string &id, const std::string &reason)
@@ -95,6 +98,19 @@ json skip(const std::string &id, const std::string &reason)
   return result;
 }
 
+json failure(const std::string &id, const std::string &reason)
+{
+  json result;
+
+  std::cerr << "FAILURE: id=" << id << " reason=" << reason << std::endl;
+
+  result["id"] = id;
+  result["actual_result"] = "FAILURE";
+  result["context"] = reason;
+
+  return result;
+}
+
 json evaluate_testcase(const json &testcase)
 {
   auto id = testcase["id"].template get<std::string>();
@@ -133,14 +149,26 @@ json evaluate_testcase(const json &testcase)
   {
     auto cert_pem = cert.template get<std::string>();
     auto cert_x509 = pem_to_x509(cert_pem);
+    if (!cert_x509)
+    {
+      return failure(id, "unable to parse trust anchor");
+    }
     // TODO(ww): Ownership is murky here, but appears to work;
     // probably because X509_STORE_add_cert does its own up-ref.
     X509_STORE_add_cert(store.get(), cert_x509.get());
   }
 
   auto untrusted = x509_stack(testcase["untrusted_intermediates"]);
+  if (!untrusted)
+  {
+    return failure(id, "unable to parse intermediate");
+  }
   auto peer_pem = testcase["peer_certificate"].template get<std::string>();
   auto peer = pem_to_x509(peer_pem);
+  if (!peer)
+  {
+    return failure(id, "unable to parse peer certificate");
+  }
   X509_STORE_CTX_ptr ctx(X509_STORE_CTX_new(), X509_STORE_CTX_free);
   X509_STORE_CTX_init(ctx.get(), store.get(), peer.get(), untrusted.get());
 
@@ -252,6 +280,7 @@ int main()
   json limbo_result = {
       {"version", 1},
       {"harness", std::string("openssl-") + HARNESS_OPENSSL_VERSION_STR},
+      {"suite_version", limbo["version"]},
       {"results", std::move(results)},
   };
   std::cout << std::setw(2) << limbo_result << std::endl;
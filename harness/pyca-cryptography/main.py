@@ -8,6 +8,7 @@ from cryptography.x509.verification import PolicyBuilder, Store, VerificationErr
 
 from limbo.models import (
     ActualResult,
+    CertProfile,
     Feature,
     KnownEKUs,
     Limbo,
@@ -41,6 +42,25 @@ LIMBO_UNSUPPORTED_FEATURES = {
     Feature.rfc5280_incompatible_with_webpki,
     # We do not support policy constraints.
     Feature.has_policy_constraints,
+    # We don't check RSA public exponents beyond what's required to load the key.
+    Feature.strict_key_params,
+    # We don't parse subscriber public keys during path validation, so we
+    # don't observe their INTEGER encodings.
+    Feature.strict_asn1_integers,
+    # We don't enforce Certificate Transparency.
+    Feature.require_embedded_sct,
+    # We reject trailing dots in both DNS SANs and expected peer names.
+    Feature.trailing_dot_matching,
+    # We reject underscores in DNS names.
+    Feature.allow_underscore_in_dns,
+    # We only match wildcards that are the entire leftmost label.
+    Feature.partial_wildcard_matching,
+    # We don't support EDIPartyName constraints.
+    Feature.edi_party_name,
+    # We reject expired EE certificates, as RFC 5280 requires.
+    Feature.allow_expired_leaf,
+    # We don't support post-quantum algorithms yet.
+    Feature.post_quantum,
 }
 
 LIMBO_SKIP_TESTCASES = {
@@ -75,13 +95,33 @@ LIMBO_SKIP_TESTCASES = {
     # with what webpki and rustls do, but inconsistent with Go and OpenSSL.
     "rfc5280::ca-as-leaf",
     "pathlen::validation-ignores-pathlen-in-leaf",
+    # We check the validity period of trust anchors, like Go and OpenSSL.
+    "rfc5280::validity::expired-root-trusted",
+    "rfc5280::validity::not-yet-valid-root-trusted",
+    # We reject v2 certificates at load time, including as trust anchors.
+    "rfc5280::uid::root-v2-unique-ids-trusted",
+    # We require trust anchors to have a BasicConstraints extension.
+    "rfc5280::root-missing-basic-constraints-trusted",
+    # We reject names whose SET OF attributes aren't in DER order at load time,
+    # before any name comparison.
+    "rfc5280::name-comparison::multi-value-rdn",
+    # We reject NameConstraints extensions with neither permitted nor
+    # excluded subtrees, as RFC 5280 forbids CAs from issuing them.
+    "rfc5280::nc::name-constraints-empty-permits-all",
+    # We enforce path length constraints on trust anchors.
+    "pathlen::root-pathlen-0-with-intermediate-trusted",
+    "pathlen::root-pathlen-1-with-two-intermediates-trusted",
 }
 
 
 def _get_limbo_peer(expected_peer: PeerName):
     match expected_peer.kind:
         case PeerKind.DNS:
-            return x509.DNSName(expected_peer.value)
+            # Per RFC 6125 6.4.2, U-labels are converted to A-labels before matching.
+            value = expected_peer.value
+            if not value.isascii():
+                value = value.encode("idna").decode()
+            return x509.DNSName(value)
         case PeerKind.IP:
             return x509.IPAddress(ipaddress.ip_address(expected_peer.value))
         case _:
@@ -99,6 +139,9 @@ def evaluate_testcase(testcase: Testcase) -> TestcaseResult:
     if LIMBO_UNSUPPORTED_FEATURES.intersection(testcase.features):
         return _skip(testcase, "testcase skipped (explicit unsupported feature)")
 
+    if testcase.profile not in (None, CertProfile.TLS):
+        return _skip(testcase, "non-TLS profiles not supported")
+
     if testcase.validation_kind != ValidationKind.SERVER:
         return _skip(testcase, "non-SERVER cases not supported yet")
 
@@ -108,22 +151,43 @@ def evaluate_testcase(testcase: Testcase) -> TestcaseResult:
     if testcase.extended_key_usage not in ([], [KnownEKUs.server_auth]):
         return _skip(testcase, "non-serverAuth EKUs not supported yet")
 
-    trusted_certs = [load_pem_x509_certificate(cert.encode()) for cert in testcase.trusted_certs]
-    untrusted_intermediates = [
-        load_pem_x509_certificate(cert.encode()) for cert in testcase.untrusted_intermediates
-    ]
+    if testcase.key_usage != []:
+        return _skip(testcase, "key usage requirements not supported yet")
 
-    peer_certificate = load_pem_x509_certificate(testcase.peer_certificate.encode())
+    try:
+        trusted_certs = [
+            load_pem_x509_certificate(cert.encode()) for cert in testcase.trusted_certs
+        ]
+        untrusted_intermediates = [
+            load_pem_x509_certificate(cert.encode()) for cert in testcase.untrusted_intermediates
+        ]
+
+        peer_certificate = load_pem_x509_certificate(testcase.peer_certificate.encode())
+    except (ValueError, x509.InvalidVersion) as e:
+        # Malformed (or non-v1/v3) certificates are rejected at load time.
+        return TestcaseResult(id=testcase.id, actual_result=ActualResult.FAILURE, context=str(e))
     peer_name = _get_limbo_peer(testcase.expected_peer_name)
 
-    builder = PolicyBuilder().store(Store(trusted_certs))
+    try:
+        store = Store(trusted_certs)
+    except ValueError as e:
+        # An empty store (i.e. no trust anchors) is rejected at construction time.
+        return TestcaseResult(id=testcase.id, actual_result=ActualResult.FAILURE, context=str(e))
+
+    builder = PolicyBuilder().store(store)
     if validation_time := testcase.validation_time:
         builder = builder.time(validation_time)
 
     if max_chain_depth := testcase.max_chain_depth:
         builder = builder.max_chain_depth(max_chain_depth)
 
-    verifier = builder.build_server_verifier(peer_name)
+    try:
+        verifier = builder.build_server_verifier(peer_name)
+    except ValueError as e:
+        # Invalid reference identifiers (e.g. with a trailing dot) are rejected
+        # before verification.
+        return TestcaseResult(id=testcase.id, actual_result=ActualResult.FAILURE, context=str(e))
+
     try:
         verifier.verify(peer_certificate, untrusted_intermediates)
         return TestcaseResult(id=testcase.id, actual_result=ActualResult.SUCCESS, context=None)
@@ -140,7 +204,10 @@ def main():
 
     print(
         LimboResult(
-            version=1, harness=f"pyca-cryptography-{pyca_version}", results=results
+            version=1,
+            harness=f"pyca-cryptography-{pyca_version}",
+            suite_version=limbo.version,
+            results=results,
         ).model_dump_json(indent=2)
     )
 
@@ -0,0 +1,394 @@
+use chrono::{DateTime, Utc};
+use limbo_harness_support::models::{
+    CertProfile, KnownEkUs, Limbo, PeerKind, ResultContextCode, SkipReason, Testcase,
+    TestcaseResult, ValidationKind,
+};
+use limbo_harness_support::unsupported::{
+    skip_known_divergence, skip_unsupported, KNOWN_DIVERGENCES, UNSUPPORTED,
+};
+use webpki::ring;
+
+/// Returns the DER-encoded contents of `eku`'s OID, which are all under
+/// `id-kp` (1.3.6.1.5.5.7.3), except for `anyExtendedKeyUsage`.
+fn eku_oid(eku: &KnownEkUs) -> &'static [u8] {
+    match eku {
+        KnownEkUs::AnyExtendedKeyUsage => &[0x55, 0x1d, 0x25, 0x00],
+        KnownEkUs::ServerAuth => &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x01],
+        KnownEkUs::ClientAuth => &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x02],
+        KnownEkUs::CodeSigning => &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x03],
+        KnownEkUs::EmailProtection => &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x04],
+        KnownEkUs::TimeStamping => &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x08],
+        KnownEkUs::OcspSigning => &[0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x03, 0x09],
+    }
+}
+
+fn der_from_pem<B: AsRef<[u8]>>(
+    bytes: B,
+) -> Result<webpki::types::CertificateDer<'static>, pem::PemError> {
+    let pem = pem::parse(bytes)?;
+    Ok(webpki::types::CertificateDer::from(pem.contents()).into_owned())
+}
+
+/// Converts any U-labels in `name` to A-labels, per RFC 6125 6.4.2.
+fn dns_name_to_ascii(name: &str) -> Option<String> {
+    if name.is_ascii() {
+        return Some(name.into());
+    }
+
+    idna::domain_to_ascii(name).ok()
+}
+
+fn render_subject_name_err(subject_name: &webpki::types::ServerName) -> String {
+    if let webpki::types::ServerName::DnsName(dns_name) = subject_name {
+        let ascii_name = dns_name.as_ref();
+        let (unicode_name, _) = idna::domain_to_unicode(ascii_name);
+        if unicode_name != ascii_name {
+            return format!("subject name validation failed for {unicode_name} ({ascii_name})");
+        }
+    }
+
+    "subject name validation failed".into()
+}
+
+/// Evaluates `tc` against rustls-webpki's end-entity certificate validation.
+pub fn evaluate_testcase(tc: &Testcase) -> TestcaseResult {
+    if let Some(skip) = skip_unsupported(tc, UNSUPPORTED) {
+        return skip;
+    }
+
+    if let Some(skip) = skip_known_divergence(tc, KNOWN_DIVERGENCES) {
+        return skip;
+    }
+
+    let usage = match tc.profile {
+        None | Some(CertProfile::Tls) => {
+            if !matches!(tc.validation_kind, ValidationKind::Server) {
+                return TestcaseResult::skip(
+                    tc,
+                    ResultContextCode::UnsupportedProfile,
+                    SkipReason::UnsupportedFeature("non-SERVER testcases not supported yet".into()),
+                );
+            }
+
+            webpki::KeyUsage::server_auth()
+        }
+        // RFC 3161 2.3 requires a TSA's EKU to be critical and to contain only
+        // timeStamping, which webpki can't check: it only checks that the
+        // required EKU is present.
+        Some(CertProfile::TimeStamping) => {
+            return TestcaseResult::skip(
+                tc,
+                ResultContextCode::UnsupportedProfile,
+                SkipReason::UnsupportedFeature(
+                    "TSA EKU requirements can't be checked through this API".into(),
+                ),
+            );
+        }
+        Some(CertProfile::Generic) => match tc.extended_key_usage.as_slice() {
+            // webpki only checks for a specific EKU, so it can't express
+            // "any EKU" as a requirement.
+            [KnownEkUs::AnyExtendedKeyUsage] => {
+                return TestcaseResult::skip(
+                    tc,
+                    ResultContextCode::UnsupportedFeature,
+                    SkipReason::UnsupportedFeature(
+                        "anyExtendedKeyUsage can't be required through this API".into(),
+                    ),
+                );
+            }
+            [eku] => webpki::KeyUsage::required(eku_oid(eku)),
+            _ => {
+                return TestcaseResult::skip(
+                    tc,
+                    ResultContextCode::UnsupportedProfile,
+                    SkipReason::UnsupportedFeature(
+                        "GENERIC testcases must require exactly one EKU".into(),
+                    ),
+                );
+            }
+        },
+        Some(_) => {
+            return TestcaseResult::skip(
+                tc,
+                ResultContextCode::UnsupportedProfile,
+                SkipReason::UnsupportedFeature(
+                    "only TLS and generic profiles are supported by this API".into(),
+                ),
+            );
+        }
+    };
+
+    if !tc.signature_algorithms.is_empty() {
+        return TestcaseResult::skip(
+            tc,
+            ResultContextCode::UnsupportedSignatureAlgorithm,
+            SkipReason::UnsupportedFeature("signature_algorithms not supported yet".into()),
+        );
+    }
+
+    if !tc.key_usage.is_empty() {
+        return TestcaseResult::skip(
+            tc,
+            ResultContextCode::UnsupportedKeyUsage,
+            SkipReason::UnsupportedFeature("key_usage not supported yet".into()),
+        );
+    }
+
+    let Ok(leaf_der) = der_from_pem(&tc.peer_certificate) else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "unable to parse peer certificate",
+        );
+    };
+    let Ok(leaf) = webpki::EndEntityCert::try_from(&leaf_der) else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "leaf cert: X.509 parse failed",
+        );
+    };
+
+    let Ok(intermediates) = tc
+        .untrusted_intermediates
+        .iter()
+        .map(der_from_pem)
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "unable to parse intermediate",
+        );
+    };
+
+    let Ok(trust_anchor_ders) = tc
+        .trusted_certs
+        .iter()
+        .map(der_from_pem)
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "unable to parse trust anchor",
+        );
+    };
+
+    // Outside of TLS, the peer can legitimately be a trust anchor (e.g. a CA
+    // that signs its own OCSP responses), but webpki always rejects a CA as
+    // the EE.
+    if matches!(tc.profile, Some(CertProfile::Generic)) && trust_anchor_ders.contains(&leaf_der) {
+        return TestcaseResult::skip(
+            tc,
+            ResultContextCode::UnsupportedFeature,
+            SkipReason::UnsupportedFeature(
+                "trust anchors can't be validated as the peer through this API".into(),
+            ),
+        );
+    }
+
+    let Ok(trust_anchors) = trust_anchor_ders
+        .iter()
+        .map(|ta| webpki::anchor_from_trusted_cert(ta))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "trusted certs: trust anchor extraction failed",
+        );
+    };
+
+    let Ok(since_epoch) =
+        (tc.validation_time.unwrap_or(Utc::now()) - DateTime::UNIX_EPOCH).to_std()
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "validation time: before the Unix epoch",
+        );
+    };
+    let validation_time = webpki::types::UnixTime::since_unix_epoch(since_epoch);
+
+    let sig_algs = &[
+        ring::ECDSA_P256_SHA256,
+        ring::ECDSA_P384_SHA384,
+        ring::RSA_PKCS1_2048_8192_SHA256,
+        ring::RSA_PKCS1_2048_8192_SHA384,
+        ring::RSA_PKCS1_2048_8192_SHA512,
+        ring::RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+        ring::RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+        ring::RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+        ring::ED25519,
+    ];
+
+    if let Err(e) = leaf.verify_for_usage(
+        sig_algs,
+        &trust_anchors,
+        &intermediates[..],
+        validation_time,
+        usage,
+        None,
+        None,
+    ) {
+        return TestcaseResult::fail(tc, ResultContextCode::PathBuildingFailed, &e.to_string());
+    }
+
+    // Outside of TLS, peers are identified by their certificates alone, so
+    // there's no peer name to check.
+    if !matches!(tc.profile, None | Some(CertProfile::Tls)) {
+        return TestcaseResult::success(tc);
+    }
+
+    let subject_name = match &tc.expected_peer_name {
+        None => {
+            return TestcaseResult::skip(
+                tc,
+                ResultContextCode::UnsupportedPeerName,
+                SkipReason::UnsupportedFeature("implementation requires peer names".into()),
+            )
+        }
+        Some(pn) => match pn.kind {
+            // rustls refuses to match names that aren't valid DNS reference
+            // identifiers, such as IP address literals.
+            PeerKind::Dns => match dns_name_to_ascii(&pn.value)
+                .and_then(|name| webpki::types::DnsName::try_from(name).ok())
+            {
+                Some(dns_name) => webpki::types::ServerName::DnsName(dns_name),
+                None => {
+                    return TestcaseResult::fail(
+                        tc,
+                        ResultContextCode::PeerNameCheckFailed,
+                        &format!("invalid DNS name: {}", pn.value),
+                    )
+                }
+            },
+            PeerKind::Ip => match pn.value.as_str().try_into() {
+                Ok(addr) => webpki::types::ServerName::IpAddress(addr),
+                Err(_) => {
+                    return TestcaseResult::fail(
+                        tc,
+                        ResultContextCode::PeerNameCheckFailed,
+                        &format!("invalid IP address: {}", pn.value),
+                    )
+                }
+            },
+            _ => {
+                return TestcaseResult::skip(
+                    tc,
+                    ResultContextCode::UnsupportedPeerName,
+                    SkipReason::UnsupportedFeature(
+                        "implementation requires DNS or IP peer names".into(),
+                    ),
+                )
+            }
+        },
+    };
+
+    if leaf
+        .verify_is_valid_for_subject_name(&subject_name)
+        .is_err()
+    {
+        TestcaseResult::fail(
+            tc,
+            ResultContextCode::PeerNameCheckFailed,
+            &render_subject_name_err(&subject_name),
+        )
+    } else {
+        TestcaseResult::success(tc)
+    }
+}
+
+/// Evaluates every testcase in `limbo`, in order.
+pub fn run_all_testcases(limbo: &Limbo) -> Vec<TestcaseResult> {
+    limbo.testcases.iter().map(evaluate_testcase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use limbo_harness_support::models::{ActualResult, PeerName};
+
+    use super::*;
+
+    const INVALID_BASE64_PEM: &str =
+        "-----BEGIN CERTIFICATE-----\n!!!!\n-----END CERTIFICATE-----\n";
+
+    /// Returns the support crate's fixture testcase, without the
+    /// `max-chain-depth` feature that this harness skips.
+    fn testcase() -> Testcase {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../harness-support/rust/tests/fixtures/limbo.json");
+        let mut tc = limbo_harness_support::load_limbo_from_path(&path)
+            .testcases
+            .pop()
+            .unwrap();
+        tc.features.clear();
+        tc.max_chain_depth = None;
+        tc
+    }
+
+    #[test]
+    fn fixture_succeeds() {
+        assert_eq!(
+            evaluate_testcase(&testcase()).actual_result,
+            ActualResult::Success
+        );
+    }
+
+    #[test]
+    fn invalid_peer_certificate_pem_fails() {
+        let mut tc = testcase();
+        tc.peer_certificate = INVALID_BASE64_PEM.into();
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn invalid_trusted_cert_pem_fails() {
+        let mut tc = testcase();
+        tc.trusted_certs.push(INVALID_BASE64_PEM.into());
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn invalid_intermediate_pem_fails() {
+        let mut tc = testcase();
+        tc.untrusted_intermediates.push(INVALID_BASE64_PEM.into());
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn invalid_dns_name_fails() {
+        let mut tc = testcase();
+        tc.expected_peer_name = Some(PeerName {
+            kind: PeerKind::Dns,
+            value: "\u{fffd}.example.com".into(),
+        });
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn invalid_ip_address_fails() {
+        let mut tc = testcase();
+        tc.expected_peer_name = Some(PeerName {
+            kind: PeerKind::Ip,
+            value: "192.0.2.256".into(),
+        });
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn pre_epoch_validation_time_fails() {
+        let mut tc = testcase();
+        tc.validation_time = Some(DateTime::UNIX_EPOCH - chrono::Duration::days(1));
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+}
@@ -0,0 +1,303 @@
+use std::time::SystemTime;
+
+use chrono::Utc;
+use limbo_harness_support::models::{
+    CertProfile, Limbo, PeerKind, ResultContextCode, SkipReason, Testcase, TestcaseResult,
+    ValidationKind,
+};
+use limbo_harness_support::unsupported::{
+    skip_known_divergence, skip_unsupported, KNOWN_DIVERGENCES, UNSUPPORTED,
+};
+
+fn render_err(e: &webpki::ErrorExt) -> String {
+    match e {
+        webpki::ErrorExt::Error(e) => e.to_string(),
+        webpki::ErrorExt::MaximumPathBuildCallsExceeded => {
+            "maximum path build calls exceeded".into()
+        }
+        webpki::ErrorExt::MaximumSignatureChecksExceeded => {
+            "maximum signature checks exceeded".into()
+        }
+        _ => "unknown error".into(),
+    }
+}
+
+/// Converts any U-labels in `name` to A-labels, per RFC 6125 6.4.2.
+fn dns_name_to_ascii(name: &str) -> Option<String> {
+    if name.is_ascii() {
+        return Some(name.into());
+    }
+
+    idna::domain_to_ascii(name).ok()
+}
+
+fn render_dns_name_err(ascii_name: &str) -> String {
+    let (unicode_name, _) = idna::domain_to_unicode(ascii_name);
+    if unicode_name == ascii_name {
+        "DNS name validation failed".into()
+    } else {
+        format!("DNS name validation failed for {unicode_name} ({ascii_name})")
+    }
+}
+
+/// Evaluates `tc` against webpki's TLS server certificate validation.
+pub fn evaluate_testcase(tc: &Testcase) -> TestcaseResult {
+    if let Some(skip) = skip_unsupported(tc, UNSUPPORTED) {
+        return skip;
+    }
+
+    if let Some(skip) = skip_known_divergence(tc, KNOWN_DIVERGENCES) {
+        return skip;
+    }
+
+    if !matches!(tc.profile, None | Some(CertProfile::Tls)) {
+        return TestcaseResult::skip(
+            tc,
+            ResultContextCode::UnsupportedProfile,
+            SkipReason::UnsupportedFeature("non-TLS profiles are not supported by this API".into()),
+        );
+    }
+
+    if !matches!(tc.validation_kind, ValidationKind::Server) {
+        return TestcaseResult::skip(
+            tc,
+            ResultContextCode::UnsupportedProfile,
+            SkipReason::UnsupportedFeature("non-SERVER testcases not supported yet".into()),
+        );
+    }
+
+    if !tc.signature_algorithms.is_empty() {
+        return TestcaseResult::skip(
+            tc,
+            ResultContextCode::UnsupportedSignatureAlgorithm,
+            SkipReason::UnsupportedFeature("signature_algorithms not supported yet".into()),
+        );
+    }
+
+    if !tc.key_usage.is_empty() {
+        return TestcaseResult::skip(
+            tc,
+            ResultContextCode::UnsupportedKeyUsage,
+            SkipReason::UnsupportedFeature("key_usage not supported yet".into()),
+        );
+    }
+
+    let Ok(leaf_der) = pem::parse(&tc.peer_certificate) else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "unable to parse peer certificate",
+        );
+    };
+    let Ok(leaf) = webpki::EndEntityCert::try_from(leaf_der.contents()) else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "leaf cert: X.509 parse failed",
+        );
+    };
+
+    let Ok(intermediates) = tc
+        .untrusted_intermediates
+        .iter()
+        .map(pem::parse)
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "unable to parse intermediate",
+        );
+    };
+
+    let Ok(trust_anchor_ders) = tc
+        .trusted_certs
+        .iter()
+        .map(pem::parse)
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "unable to parse trust anchor",
+        );
+    };
+
+    let Ok(trust_anchors) = trust_anchor_ders
+        .iter()
+        .map(|ta| webpki::TrustAnchor::try_from_cert_der(ta.contents()))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "trusted certs: trust anchor extraction failed",
+        );
+    };
+
+    let Ok(validation_time) =
+        webpki::Time::try_from(SystemTime::from(tc.validation_time.unwrap_or(Utc::now())))
+    else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::ParseError,
+            "validation time: conversion to webpki::Time failed",
+        );
+    };
+
+    let sig_algs = &[
+        &webpki::ECDSA_P256_SHA256,
+        &webpki::ECDSA_P384_SHA384,
+        &webpki::RSA_PKCS1_2048_8192_SHA256,
+        &webpki::RSA_PKCS1_2048_8192_SHA384,
+        &webpki::RSA_PKCS1_2048_8192_SHA512,
+        &webpki::RSA_PSS_2048_8192_SHA256_LEGACY_KEY,
+        &webpki::RSA_PSS_2048_8192_SHA384_LEGACY_KEY,
+        &webpki::RSA_PSS_2048_8192_SHA512_LEGACY_KEY,
+        &webpki::ED25519,
+    ];
+
+    if let Err(e) = leaf.verify_is_valid_tls_server_cert_ext(
+        sig_algs,
+        &webpki::TlsServerTrustAnchors(&trust_anchors),
+        &intermediates
+            .iter()
+            .map(|ic| ic.contents())
+            .collect::<Vec<_>>(),
+        validation_time,
+    ) {
+        return TestcaseResult::fail(tc, ResultContextCode::PathBuildingFailed, &render_err(&e));
+    }
+
+    let peer_name = match &tc.expected_peer_name {
+        None => {
+            return TestcaseResult::skip(
+                tc,
+                ResultContextCode::UnsupportedPeerName,
+                SkipReason::UnsupportedFeature("implementation requires peer names".into()),
+            )
+        }
+        Some(pn) => match pn.kind {
+            PeerKind::Dns => &pn.value,
+            _ => {
+                return TestcaseResult::skip(
+                    tc,
+                    ResultContextCode::UnsupportedPeerName,
+                    SkipReason::UnsupportedFeature("implementation requires DNS peer names".into()),
+                )
+            }
+        },
+    };
+    // webpki refuses to match names that aren't valid DNS reference
+    // identifiers, such as IP address literals.
+    let Some(ascii_name) = dns_name_to_ascii(peer_name) else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::PeerNameCheckFailed,
+            &format!("invalid DNS name: {peer_name}"),
+        );
+    };
+    let Ok(dns_name) = webpki::DnsNameRef::try_from_ascii_str(&ascii_name) else {
+        return TestcaseResult::fail(
+            tc,
+            ResultContextCode::PeerNameCheckFailed,
+            &format!("invalid DNS name: {ascii_name}"),
+        );
+    };
+
+    if leaf.verify_is_valid_for_dns_name(dns_name).is_err() {
+        TestcaseResult::fail(
+            tc,
+            ResultContextCode::PeerNameCheckFailed,
+            &render_dns_name_err(&ascii_name),
+        )
+    } else {
+        TestcaseResult::success(tc)
+    }
+
+    // We're not actually initiating a TLS connection, so we don't
+    // perform `EndEntityCert.verify_signature`.
+}
+
+/// Evaluates every testcase in `limbo`, in order.
+pub fn run_all_testcases(limbo: &Limbo) -> Vec<TestcaseResult> {
+    limbo.testcases.iter().map(evaluate_testcase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use chrono::DateTime;
+    use limbo_harness_support::models::{ActualResult, PeerName};
+
+    use super::*;
+
+    const INVALID_BASE64_PEM: &str =
+        "-----BEGIN CERTIFICATE-----\n!!!!\n-----END CERTIFICATE-----\n";
+
+    /// Returns the support crate's fixture testcase, without the
+    /// `max-chain-depth` feature that this harness skips.
+    fn testcase() -> Testcase {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../harness-support/rust/tests/fixtures/limbo.json");
+        let mut tc = limbo_harness_support::load_limbo_from_path(&path)
+            .testcases
+            .pop()
+            .unwrap();
+        tc.features.clear();
+        tc.max_chain_depth = None;
+        tc
+    }
+
+    #[test]
+    fn fixture_succeeds() {
+        assert_eq!(
+            evaluate_testcase(&testcase()).actual_result,
+            ActualResult::Success
+        );
+    }
+
+    #[test]
+    fn invalid_peer_certificate_pem_fails() {
+        let mut tc = testcase();
+        tc.peer_certificate = INVALID_BASE64_PEM.into();
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn invalid_trusted_cert_pem_fails() {
+        let mut tc = testcase();
+        tc.trusted_certs.push(INVALID_BASE64_PEM.into());
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn invalid_intermediate_pem_fails() {
+        let mut tc = testcase();
+        tc.untrusted_intermediates.push(INVALID_BASE64_PEM.into());
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn invalid_dns_name_fails() {
+        let mut tc = testcase();
+        tc.expected_peer_name = Some(PeerName {
+            kind: PeerKind::Dns,
+            value: "\u{fffd}.example.com".into(),
+        });
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+
+    #[test]
+    fn pre_epoch_validation_time_fails() {
+        let mut tc = testcase();
+        tc.validation_time = Some(DateTime::UNIX_EPOCH - chrono::Duration::days(1));
+
+        assert_eq!(evaluate_testcase(&tc).actual_result, ActualResult::Failure);
+    }
+}
@@ -4,15 +4,18 @@ Models and definitions for generating certificate assets for Limbo testcases.
 
 from __future__ import annotations
 
+import base64
 import datetime
 import logging
+import textwrap
 from dataclasses import dataclass
 from functools import cached_property
 from importlib import resources
-from typing import Generic, TypeVar
+from typing import Callable, Generic, TypeVar
 
 from cryptography import x509
-from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec, ed448, ed25519, padding, rsa
 from cryptography.hazmat.primitives.asymmetric.types import CertificateIssuerPrivateKeyTypes
 from cryptography.x509 import ExtensionType
 
@@ -51,13 +54,48 @@ class CertificatePair(Certificate):
 
     @cached_property
     def key_pem(self) -> str:
+        # EdDSA keys have no "traditional" encoding, only PKCS#8.
+        if isinstance(self.key, (ed25519.Ed25519PrivateKey, ed448.Ed448PrivateKey)):
+            format = serialization.PrivateFormat.PKCS8
+        else:
+            format = serialization.PrivateFormat.TraditionalOpenSSL
+
         return self.key.private_bytes(
             encoding=serialization.Encoding.PEM,
-            format=serialization.PrivateFormat.TraditionalOpenSSL,
+            format=format,
             encryption_algorithm=serialization.NoEncryption(),
         ).decode()
 
 
+@dataclass(frozen=True)
+class RawCertificate:
+    """
+    A raw DER-encoded X.509 certificate.
+
+    Unlike `Certificate`, this can represent certificates that are malformed
+    in ways that `cryptography` refuses to produce or load.
+    """
+
+    der: bytes
+
+    @cached_property
+    def cert_pem(self) -> str:
+        body = "\n".join(textwrap.wrap(base64.b64encode(self.der).decode(), 64))
+        return f"-----BEGIN CERTIFICATE-----\n{body}\n-----END CERTIFICATE-----\n"
+
+
+@dataclass(frozen=True)
+class RawPEM:
+    """
+    An arbitrary string, supplied in place of a PEM-encoded certificate.
+
+    Unlike `RawCertificate`, this can represent input that isn't a PEM
+    certificate at all, e.g. a PEM block of another type.
+    """
+
+    cert_pem: str
+
+
 @dataclass(frozen=True)
 class _Extension(Generic[_ExtensionType]):
     """
@@ -73,3 +111,130 @@ def ext(extension: _ExtensionType, *, critical: bool) -> _Extension[_ExtensionTy
     Constructs a new _Extension to pass into certificate builder helpers.
     """
     return _Extension(extension, critical)
+
+
+def der_tlv(tag: int, content: bytes) -> bytes:
+    """
+    Encodes a single DER TLV with the given (single-byte) tag and contents.
+    """
+    if len(content) < 0x80:
+        return bytes([tag, len(content)]) + content
+    length = len(content).to_bytes((len(content).bit_length() + 7) // 8, "big")
+    return bytes([tag, 0x80 | len(length)]) + length + content
+
+
+def _der_read(data: bytes) -> tuple[bytes, bytes, bytes]:
+    """
+    Reads a single DER TLV from the front of `data`, returning the complete
+    TLV, its contents, and any remaining data.
+    """
+    length = data[1]
+    offset = 2
+    if length & 0x80:
+        offset += length & 0x7F
+        length = int.from_bytes(data[2:offset], "big")
+    return data[: offset + length], data[offset : offset + length], data[offset + length :]
+
+
+def der_children(der: bytes) -> list[bytes]:
+    """
+    Splits a DER-encoded constructed value (e.g. a SEQUENCE) into the complete
+    TLVs of its immediate children.
+    """
+    _, content, rest = _der_read(der)
+    if rest:
+        raise ValueError("trailing data after DER value")
+
+    children = []
+    while content:
+        child, _, content = _der_read(content)
+        children.append(child)
+    return children
+
+
+# The indices of the fields within a v3 `tbsCertificate`, as passed to the
+# `tbs` callback of `tamper`.
+TBS_VERSION_INDEX = 0
+TBS_SERIAL_INDEX = 1
+TBS_SIGNATURE_INDEX = 2
+TBS_ISSUER_INDEX = 3
+TBS_VALIDITY_INDEX = 4
+TBS_SUBJECT_INDEX = 5
+TBS_SPKI_INDEX = 6
+TBS_EXTENSIONS_INDEX = 7
+
+
+def replace_tbs_field(index: int, value: bytes) -> Callable[[list[bytes]], list[bytes]]:
+    """
+    Returns a `tbs` callback for `tamper` that replaces the `tbsCertificate`
+    field at `index` with `value`, which must be a complete DER TLV.
+    """
+
+    def _tbs(fields: list[bytes]) -> list[bytes]:
+        fields[index] = value
+        return fields
+
+    return _tbs
+
+
+# DER-encoded `AlgorithmIdentifier`s for the signature algorithms that
+# `tamper` can re-sign with, keyed by hash algorithm name.
+_RSA_SIGNATURE_ALGORITHMS = {
+    "sha1": bytes.fromhex("300d06092a864886f70d0101050500"),
+    "sha256": bytes.fromhex("300d06092a864886f70d01010b0500"),
+}
+_ECDSA_SIGNATURE_ALGORITHMS = {
+    "sha1": bytes.fromhex("300906072a8648ce3d0401"),
+    "sha256": bytes.fromhex("300a06082a8648ce3d040302"),
+}
+
+
+def tamper(
+    cert: Certificate,
+    signer: CertificateIssuerPrivateKeyTypes,
+    *,
+    tbs: Callable[[list[bytes]], list[bytes]] | None = None,
+    signature_hash_algorithm: hashes.HashAlgorithm | None = None,
+) -> RawCertificate:
+    """
+    Re-encodes and re-signs `cert` after applying `tbs` to the DER-encoded
+    fields of its `tbsCertificate`.
+
+    `signer` must be the same type of key that originally signed `cert`;
+    passing a different key of that type produces a bad signature. If
+    `signature_hash_algorithm` is given, `cert` is re-signed with it instead
+    of its original hash algorithm; this allows for signatures (like SHA-1)
+    that `cryptography` refuses to produce.
+    """
+    fields = der_children(cert.cert.tbs_certificate_bytes)
+    _, signature_algorithm, _ = der_children(cert.cert.public_bytes(serialization.Encoding.DER))
+
+    hash_algorithm = cert.cert.signature_hash_algorithm
+    if signature_hash_algorithm is not None:
+        hash_algorithm = signature_hash_algorithm
+        if isinstance(signer, rsa.RSAPrivateKey):
+            signature_algorithm = _RSA_SIGNATURE_ALGORITHMS[hash_algorithm.name]
+        elif isinstance(signer, ec.EllipticCurvePrivateKey):
+            signature_algorithm = _ECDSA_SIGNATURE_ALGORITHMS[hash_algorithm.name]
+        else:
+            raise TypeError(f"can't re-sign with a hash algorithm: {type(signer)}")
+        fields[TBS_SIGNATURE_INDEX] = signature_algorithm
+
+    if tbs is not None:
+        fields = tbs(fields)
+    tbs_der = der_tlv(0x30, b"".join(fields))
+
+    if isinstance(signer, ec.EllipticCurvePrivateKey):
+        assert isinstance(hash_algorithm, hashes.HashAlgorithm)
+        signature = signer.sign(tbs_der, ec.ECDSA(hash_algorithm))
+    elif isinstance(signer, rsa.RSAPrivateKey):
+        assert isinstance(hash_algorithm, hashes.HashAlgorithm)
+        signature = signer.sign(tbs_der, padding.PKCS1v15(), hash_algorithm)
+    elif isinstance(signer, (ed25519.Ed25519PrivateKey, ed448.Ed448PrivateKey)):
+        signature = signer.sign(tbs_der)
+    else:
+        raise TypeError(f"unsupported signer: {type(signer)}")
+
+    return RawCertificate(
+        der_tlv(0x30, tbs_der + signature_algorithm + der_tlv(0x03, b"\x00" + signature))
+    )
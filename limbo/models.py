@@ -43,6 +43,7 @@ class PeerKind(str, Enum):
     RFC822 = "RFC822"
     DNS = "DNS"
     IP = "IP"
+    UPN = "UPN"
 
 
 class PeerName(BaseModel):
@@ -215,6 +216,100 @@ class Feature(str, Enum):
     Tests that exercise DoS resiliency.
     """
 
+    strict_der = "strict-der"
+    """
+    Tests that exercise strict DER encoding requirements, such as rejecting
+    BER-only encodings. Many implementations tolerate some non-DER encodings.
+    """
+
+    strict_key_params = "strict-key-params"
+    """
+    Tests that exercise strict public key parameter checks beyond the CABF
+    requirements, such as requiring an RSA public exponent of at least 65537.
+    """
+
+    strict_asn1_integers = "strict-asn1-integers"
+    """
+    Tests that exercise strict ASN.1 INTEGER encoding checks, such as rejecting
+    redundant leading zero bytes in public key parameters.
+    """
+
+    require_embedded_sct = "require-embedded-sct"
+    """
+    For implementations that enforce Certificate Transparency by requiring
+    valid embedded SCTs (RFC 6962) in EE certificates.
+    """
+
+    strict_anchor_validity = "strict-anchor-validity"
+    """
+    For implementations that check the validity period of trust anchors.
+    RFC 5937 4 permits, but does not require, relying parties to do so.
+    """
+
+    trailing_dot_matching = "trailing-dot-matching"
+    """
+    For implementations that treat a trailing dot (the DNS root label) in a DNS name as
+    insignificant when matching names, e.g. matching `example.com.` against `example.com`.
+    """
+
+    allow_underscore_in_dns = "allow-underscore-in-dns"
+    """
+    For implementations that accept underscores in DNS names, as is common for internal
+    CAs (e.g. `_dmarc.example.com`), despite the LDH syntax required by RFC 5280 and the Web PKI.
+    """
+
+    partial_wildcard_matching = "partial-wildcard-matching"
+    """
+    For implementations that match wildcards that are only part of the leftmost label,
+    e.g. `*foo.example.com`, as RFC 6125 permitted but RFC 9525 forbids.
+    """
+
+    strict_anchor_constraints = "strict-anchor-constraints"
+    """
+    For implementations that require trust anchors to be well-formed CA
+    certificates, e.g. with a critical BasicConstraints extension asserting
+    cA, and that enforce the constraints in them, e.g. its path length.
+    RFC 5937 4 permits relying parties to treat a trust anchor as trusted
+    regardless of its extensions.
+    """
+
+    large_store = "large-store"
+    """
+    Tests that supply very large numbers of certificates, for measuring the
+    performance of path building. These are slow to evaluate, and may be
+    skipped for faster feedback.
+    """
+
+    edi_party_name = "edi-party-name"
+    """
+    For implementations that process name constraints on the `ediPartyName`
+    name form. This form is very rarely used, and most implementations do not
+    support it.
+    """
+
+    allow_expired_leaf = "allow-expired-leaf"
+    """
+    For implementations that accept an EE certificate whose validity period has
+    ended, e.g. for archival validation of signatures made while it was valid.
+    RFC 5280 6.1.3 requires every certificate in the path, including the EE,
+    to be valid at the validation time.
+    """
+
+    reject_v2 = "reject-v2"
+    """
+    For implementations that reject X.509 v2 certificates, including as trust
+    anchors. RFC 5280 4.1.2.8 forbids conforming CAs from issuing certificates
+    with unique identifiers, which require v2 or later, but places no processing
+    requirements on them.
+    """
+
+    post_quantum = "post-quantum"
+    """
+    For implementations that support post-quantum public key and signature
+    algorithms, such as ML-DSA (FIPS 204) and ML-KEM (FIPS 203), including as
+    alternative signatures (ITU-T X.509 (2019) 9.8).
+    """
+
 
 class Importance(str, Enum):
     """
@@ -260,6 +355,18 @@ class ValidationKind(str, Enum):
     SERVER = "SERVER"
 
 
+class CertProfile(str, Enum):
+    """
+    The X.509 application profile that a testcase exercises.
+    """
+
+    TLS = "TLS"
+    SMIME = "SMIME"
+    CODE_SIGNING = "CODE_SIGNING"
+    TIME_STAMPING = "TIME_STAMPING"
+    GENERIC = "GENERIC"
+
+
 class Testcase(BaseModel):
     """
     Represents an individual Limbo testcase.
@@ -332,6 +439,14 @@ class Testcase(BaseModel):
 
     max_chain_depth: int | None = Field(None, description="The maximum chain-building depth")
 
+    profile: CertProfile | None = Field(
+        None,
+        description=(
+            "The application profile that this testcase exercises; if unset, the testcase "
+            "exercises the TLS (Web PKI) profile"
+        ),
+    )
+
     @field_validator("validation_time")
     @classmethod
     def validate_validation_time(cls, v: datetime | None) -> datetime | None:
@@ -399,6 +514,49 @@ class Limbo(BaseModel):
         return {tc.id: tc for tc in self.testcases}
 
 
+class SkipReasonKind(str, Enum):
+    """
+    Machine-readable categories for SKIPPED results.
+    """
+
+    UNSUPPORTED_FEATURE = "unsupported-feature"
+    KNOWN_BUG = "known-bug"
+    LINTER_CHECK = "linter-check"
+    TIMEOUT = "timeout"
+    BROKEN_TESTCASE = "broken-testcase"
+    NOT_EVALUATED = "not-evaluated"
+
+
+class SkipReason(BaseModel):
+    """
+    Represents why a testcase was skipped.
+    """
+
+    kind: SkipReasonKind = Field(..., description="The category of skip")
+
+    detail: StrictStr | None = Field(None, description="Any additional detail for the skip")
+
+
+class ResultContextCode(str, Enum):
+    """
+    Machine-readable categories for a result's context.
+    """
+
+    UNSUPPORTED_FEATURE = "unsupported-feature"
+    UNSUPPORTED_PROFILE = "unsupported-profile"
+    UNSUPPORTED_SIGNATURE_ALGORITHM = "unsupported-signature-algorithm"
+    UNSUPPORTED_KEY_USAGE = "unsupported-key-usage"
+    UNSUPPORTED_PEER_NAME = "unsupported-peer-name"
+    PARSE_ERROR = "parse-error"
+    PATH_BUILDING_FAILED = "path-building-failed"
+    PEER_NAME_CHECK_FAILED = "peer-name-check-failed"
+    KNOWN_BUG = "known-bug"
+    LINTER_CHECK = "linter-check"
+    TIMEOUT = "timeout"
+    BROKEN_TESTCASE = "broken-testcase"
+    NOT_EVALUATED = "not-evaluated"
+
+
 class TestcaseResult(BaseModel):
     """
     Represents the outcome of evaluating a testcase.
@@ -418,6 +576,38 @@ class TestcaseResult(BaseModel):
         ..., description="Any context for FAILURE or SKIPPED results; can be multiple lines"
     )
 
+    context_code: ResultContextCode | None = Field(
+        None, description="A machine-readable category for `context`, if the harness provides one"
+    )
+
+    skip_reason: SkipReason | None = Field(
+        None, description="For SKIPPED results, a machine-readable reason for the skip"
+    )
+
+    description: StrictStr | None = Field(
+        None, description="The evaluated testcase's description, if the harness included it"
+    )
+
+
+class HarnessMetadata(BaseModel):
+    """
+    Information about the environment that produced a set of results.
+    """
+
+    harness_version: StrictStr = Field(..., description="The harness's own version")
+
+    rust_version: StrictStr = Field(
+        ..., description="The toolchain the harness was built with, for Rust harnesses"
+    )
+
+    os: StrictStr = Field(..., description="The operating system the harness ran on")
+
+    timestamp_utc: StrictStr = Field(
+        ..., description="When the harness was run, as an RFC 3339 timestamp"
+    )
+
+    limbo_suite_version: int = Field(..., description="The version of the evaluated limbo suite")
+
 
 class LimboResult(BaseModel):
     """
@@ -432,10 +622,18 @@ class LimboResult(BaseModel):
         ..., description="A short, unique identifier for the harness that produced these results"
     )
 
+    suite_version: int = Field(
+        ..., description="The `version` of the limbo testsuite that these results are for"
+    )
+
     results: list[TestcaseResult] = Field(
         ..., description="One or more results for testcase evaluations"
     )
 
+    metadata: HarnessMetadata | None = Field(
+        None, description="Information about the environment that produced these results"
+    )
+
     @cached_property
     def by_id(self) -> dict[TestCaseID, TestcaseResult]:
         """
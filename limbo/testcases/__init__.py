@@ -4,10 +4,19 @@ Models and definitions for generating Limbo testcases.
 
 from limbo.testcases._core import registry
 
+from .algorithms import *  # noqa: F403
+from .codesigning import *  # noqa: F403
 from .cve import *  # noqa: F403
+from .ocsp import *  # noqa: F403
+from .pathbuilding import *  # noqa: F403
 from .pathlen import *  # noqa: F403
 from .pathological import *  # noqa: F403
 from .rfc5280 import *  # noqa: F403
+from .rfc6962 import *  # noqa: F403
+from .rfc7468 import *  # noqa: F403
+from .rfc7633 import *  # noqa: F403
+from .smime import *  # noqa: F403
+from .timestamping import *  # noqa: F403
 from .webpki import *  # noqa: F403
 
 __all__ = [
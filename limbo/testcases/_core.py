@@ -7,7 +7,7 @@ from typing import Callable, Literal, Self
 
 from cryptography import x509
 from cryptography.hazmat.primitives import hashes
-from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.asymmetric import ec, ed448, ed25519
 from cryptography.hazmat.primitives.asymmetric.types import CertificateIssuerPrivateKeyTypes
 
 from limbo.assets import (
@@ -15,10 +15,13 @@ from limbo.assets import (
     ONE_THOUSAND_YEARS_OF_TORMENT,
     Certificate,
     CertificatePair,
+    RawCertificate,
+    RawPEM,
     _Extension,
     ext,
 )
 from limbo.models import (
+    CertProfile,
     Feature,
     Importance,
     KeyUsage,
@@ -31,6 +34,13 @@ from limbo.models import (
 logger = logging.getLogger(__name__)
 
 
+def _signature_hash_algorithm(signer: CertificateIssuerPrivateKeyTypes) -> hashes.SHA256 | None:
+    # EdDSA signatures don't take a separate hash algorithm.
+    if isinstance(signer, (ed25519.Ed25519PrivateKey, ed448.Ed448PrivateKey)):
+        return None
+    return hashes.SHA256()
+
+
 class Builder:
     def _ca(
         self,
@@ -47,6 +57,7 @@ class Builder:
         ski: _Extension[x509.SubjectKeyIdentifier] | Literal[True] | None,
         name_constraints: _Extension[x509.NameConstraints] | None,
         extra_extension: _Extension[x509.ExtensionType] | None,
+        extra_unchecked_extensions: list[_Extension] | None,
         parent: CertificatePair | None,
     ) -> CertificatePair:
         if subject is None:
@@ -114,10 +125,13 @@ class Builder:
         if extra_extension:
             builder = builder.add_extension(extra_extension.ext, critical=extra_extension.critical)
 
-        if parent:
-            cert = builder.sign(parent.key, algorithm=hashes.SHA256())
-        else:
-            cert = builder.sign(key, algorithm=hashes.SHA256())
+        if extra_unchecked_extensions is not None:
+            # NOTE: Add extension manually to bypass validation.
+            for e in extra_unchecked_extensions:
+                builder._extensions.append(x509.Extension(e.ext.oid, e.critical, e.ext))
+
+        signer = parent.key if parent else key
+        cert = builder.sign(signer, algorithm=_signature_hash_algorithm(signer))
 
         return CertificatePair(cert, key)
 
@@ -153,6 +167,7 @@ class Builder:
         ski: _Extension[x509.SubjectKeyIdentifier] | Literal[True] | None = True,
         name_constraints: _Extension[x509.NameConstraints] | None = None,
         extra_extension: _Extension | None = None,
+        extra_unchecked_extensions: list[_Extension] | None = None,
     ) -> CertificatePair:
         return self._ca(
             issuer,
@@ -168,6 +183,7 @@ class Builder:
             ski,
             name_constraints,
             extra_extension,
+            extra_unchecked_extensions,
             None,
         )
 
@@ -202,6 +218,7 @@ class Builder:
         ski: _Extension[x509.SubjectKeyIdentifier] | Literal[True] | None = True,
         name_constraints: _Extension[x509.NameConstraints] | None = None,
         extra_extension: _Extension[x509.ExtensionType] | None = None,
+        extra_unchecked_extensions: list[_Extension] | None = None,
     ) -> CertificatePair:
         """
         An intermediate CA chained up to a root CA.
@@ -244,6 +261,7 @@ class Builder:
             ski,
             name_constraints,
             extra_extension,
+            extra_unchecked_extensions,
             parent,
         )
 
@@ -359,7 +377,7 @@ class Builder:
 
         certificate = builder.sign(
             private_key=parent.key,
-            algorithm=hashes.SHA256(),
+            algorithm=_signature_hash_algorithm(parent.key),
         )
 
         return CertificatePair(certificate, key)
@@ -384,6 +402,7 @@ class Builder:
         self._expected_peer_name: PeerName | None = PeerName(kind="DNS", value="example.com")
         self._expected_peer_names: list[PeerName] = []
         self._max_chain_depth: int | None = None
+        self._profile: CertProfile | None = None
 
     def conflicts_with(self, *conflicting_ids: str) -> Self:
         self._conflicts_with = list(conflicting_ids)
@@ -405,15 +424,17 @@ class Builder:
         self._validation_kind = "SERVER"
         return self
 
-    def trusted_certs(self, *certs: Certificate) -> Self:
+    def trusted_certs(self, *certs: Certificate | RawCertificate | RawPEM) -> Self:
         self._trusted_certs = [c.cert_pem for c in certs]
         return self
 
-    def untrusted_intermediates(self, *certs: Certificate) -> Self:
+    def untrusted_intermediates(self, *certs: Certificate | RawCertificate | RawPEM) -> Self:
         self._untrusted_intermediates = [c.cert_pem for c in certs]
         return self
 
-    def peer_certificate(self, cert: Certificate | CertificatePair) -> Self:
+    def peer_certificate(
+        self, cert: Certificate | CertificatePair | RawCertificate | RawPEM
+    ) -> Self:
         self._peer_certificate = cert.cert_pem
 
         if isinstance(cert, CertificatePair):
@@ -444,7 +465,7 @@ class Builder:
         self._expected_result = "FAILURE"
         return self
 
-    def expected_peer_name(self, name: PeerName) -> Self:
+    def expected_peer_name(self, name: PeerName | None) -> Self:
         self._expected_peer_name = name
         return self
 
@@ -456,6 +477,10 @@ class Builder:
         self._max_chain_depth = max_chain_depth
         return self
 
+    def profile(self, profile: CertProfile) -> Self:
+        self._profile = profile
+        return self
+
     def build(self) -> Testcase:
         return Testcase(
             id=self._id,
@@ -476,6 +501,7 @@ class Builder:
             expected_peer_name=self._expected_peer_name,
             expected_peer_names=self._expected_peer_names,
             max_chain_depth=self._max_chain_depth,
+            profile=self._profile,
         )
 
 
@@ -0,0 +1,542 @@
+"""
+Signature and public key algorithm testcases.
+"""
+
+import os
+import random
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec, ed448, ed25519, rsa
+
+from limbo.assets import TBS_SPKI_INDEX, der_tlv, ext, replace_tbs_field, tamper
+from limbo.models import CertProfile, Feature, Importance, KeyUsage, SignatureAlgorithm
+from limbo.testcases._core import Builder, testcase
+
+# AlgorithmIdentifier ::= SEQUENCE { id-ml-dsa-44 } (FIPS 204, parameters absent)
+_ML_DSA_44 = bytes.fromhex("300b0609608648016503040311")
+# AlgorithmIdentifier ::= SEQUENCE { id-alg-ml-kem-768 } (FIPS 203, parameters absent)
+_ML_KEM_768 = bytes.fromhex("300b0609608648016503040402")
+
+# ITU-T X.509 (2019) 9.8 alternative public key and signature extensions.
+_SUBJECT_ALT_PUBLIC_KEY_INFO = x509.ObjectIdentifier("2.5.29.72")
+_ALT_SIGNATURE_ALGORITHM = x509.ObjectIdentifier("2.5.29.73")
+_ALT_SIGNATURE_VALUE = x509.ObjectIdentifier("2.5.29.74")
+
+
+def _spki(algorithm: bytes, public_key: bytes) -> bytes:
+    # SubjectPublicKeyInfo ::= SEQUENCE { algorithm, BIT STRING }
+    return der_tlv(0x30, algorithm + der_tlv(0x03, b"\x00" + public_key))
+
+
+def _stub_ml_dsa_44_key() -> bytes:
+    """
+    Produces a random ML-DSA-44 public key. Every 1312-byte string is a
+    well-formed ML-DSA-44 public key, so this is indistinguishable from a
+    real one until it's used to verify something.
+    """
+
+    return os.urandom(1312)
+
+
+def _stub_ml_kem_768_key() -> bytes:
+    """
+    Produces a random ML-KEM-768 encapsulation key. Unlike ML-DSA, not every
+    byte string is a well-formed key: FIPS 203 7.2 requires each of the
+    key's 12-bit coefficients to be reduced modulo q = 3329.
+    """
+
+    t = [random.randrange(3329) for _ in range(3 * 256)]
+    encoded = b"".join(
+        bytes([a & 0xFF, (a >> 8) | ((b & 0xF) << 4), b >> 4]) for a, b in zip(t[::2], t[1::2])
+    )
+    return encoded + os.urandom(32)
+
+
+@testcase
+def mixed_algorithm_chain(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (RSA-2048) -> ICA (P-256) -> EE (Ed25519)
+    ```
+
+    Each certificate in the chain conveys a different public key algorithm,
+    so the root signs the ICA with RSA and the ICA signs the EE with ECDSA.
+    Path builders should not assume that every certificate in a chain uses
+    the same algorithm.
+    """
+
+    root = builder.root_ca(key=rsa.generate_private_key(public_exponent=65537, key_size=2048))
+    ica = builder.intermediate_ca(root, key=ec.generate_private_key(ec.SECP256R1()))
+    leaf = builder.leaf_cert(ica, key=ed25519.Ed25519PrivateKey.generate())
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def mixed_algorithm_chain_disallowed_algorithm(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (RSA-2048) -> ICA (P-256) -> EE (Ed25519)
+    ```
+
+    This is the same chain as `mixed-algorithm-chain`, but the acceptable
+    signature algorithms are limited to `ECDSA_WITH_SHA256`. The root's
+    `RSA_WITH_SHA256` signature over the ICA is therefore not acceptable,
+    even though the ICA's signature over the EE is.
+    """
+
+    root = builder.root_ca(key=rsa.generate_private_key(public_exponent=65537, key_size=2048))
+    ica = builder.intermediate_ca(root, key=ec.generate_private_key(ec.SECP256R1()))
+    leaf = builder.leaf_cert(ica, key=ed25519.Ed25519PrivateKey.generate())
+
+    builder = (
+        builder.server_validation()
+        .signature_algorithms([SignatureAlgorithm.ECDSA_WITH_SHA256])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def sha1_rsa_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **ambiguous** chain:
+
+    ```
+    root (RSA-2048) -> ICA -> EE
+    ```
+
+    The root signs the ICA with `sha1WithRSAEncryption`
+    (OID 1.2.840.113549.1.1.5). No signature algorithm constraints are
+    given, so this chain is valid unless the implementation has a global
+    policy against SHA-1 (as most modern implementations do).
+    """
+
+    root = builder.root_ca(key=rsa.generate_private_key(public_exponent=65537, key_size=2048))
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+    ica_sha1 = tamper(ica, root.key, signature_hash_algorithm=hashes.SHA1())
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica_sha1)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def sha1_rsa_intermediate_disallowed(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (RSA-2048) -> ICA -> EE
+    ```
+
+    The root signs the ICA with `sha1WithRSAEncryption`
+    (OID 1.2.840.113549.1.1.5), but the acceptable signature algorithms
+    are limited to SHA-2 based algorithms.
+    """
+
+    root = builder.root_ca(key=rsa.generate_private_key(public_exponent=65537, key_size=2048))
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+    ica_sha1 = tamper(ica, root.key, signature_hash_algorithm=hashes.SHA1())
+
+    builder = (
+        builder.server_validation()
+        .signature_algorithms(
+            [SignatureAlgorithm.RSA_WITH_SHA256, SignatureAlgorithm.ECDSA_WITH_SHA256]
+        )
+        .trusted_certs(root)
+        .untrusted_intermediates(ica_sha1)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def sha1_ecdsa_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **ambiguous** chain:
+
+    ```
+    root (P-256) -> ICA -> EE
+    ```
+
+    The root signs the ICA with `ecdsa-with-SHA1` (OID 1.2.840.10045.4.1).
+    No signature algorithm constraints are given, so this chain is valid
+    unless the implementation has a global policy against SHA-1 (as most
+    modern implementations do).
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+    ica_sha1 = tamper(ica, root.key, signature_hash_algorithm=hashes.SHA1())
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica_sha1)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def sha1_ecdsa_intermediate_disallowed(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (P-256) -> ICA -> EE
+    ```
+
+    The root signs the ICA with `ecdsa-with-SHA1` (OID 1.2.840.10045.4.1),
+    but the acceptable signature algorithms are limited to SHA-2 based
+    algorithms.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+    ica_sha1 = tamper(ica, root.key, signature_hash_algorithm=hashes.SHA1())
+
+    builder = (
+        builder.server_validation()
+        .signature_algorithms(
+            [SignatureAlgorithm.RSA_WITH_SHA256, SignatureAlgorithm.ECDSA_WITH_SHA256]
+        )
+        .trusted_certs(root)
+        .untrusted_intermediates(ica_sha1)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def sha1_rsa_root_disallowed(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (RSA-2048) -> EE
+    ```
+
+    The root is self-signed with `sha1WithRSAEncryption`, and the acceptable
+    signature algorithms are limited to SHA-2 based algorithms. The root
+    signs the EE with `sha256WithRSAEncryption`.
+
+    A trust anchor's self-signature is not part of path validation
+    (RFC 5280 6.1), so the SHA-1 signature on the root is irrelevant and
+    this chain is valid.
+    """
+
+    root = builder.root_ca(key=rsa.generate_private_key(public_exponent=65537, key_size=2048))
+    leaf = builder.leaf_cert(root)
+    root_sha1 = tamper(root, root.key, signature_hash_algorithm=hashes.SHA1())
+
+    builder = (
+        builder.server_validation()
+        .signature_algorithms(
+            [SignatureAlgorithm.RSA_WITH_SHA256, SignatureAlgorithm.ECDSA_WITH_SHA256]
+        )
+        .trusted_certs(root_sha1)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def ed25519_chain(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (Ed25519) -> ICA (Ed25519) -> EE (Ed25519)
+    ```
+
+    Every certificate conveys an Ed25519 key (OID 1.3.101.112), and every
+    signature in the chain is an Ed25519 signature.
+    """
+
+    root = builder.root_ca(key=ed25519.Ed25519PrivateKey.generate())
+    ica = builder.intermediate_ca(root, key=ed25519.Ed25519PrivateKey.generate())
+    leaf = builder.leaf_cert(ica, key=ed25519.Ed25519PrivateKey.generate())
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def ed448_chain(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (Ed448) -> ICA (Ed448) -> EE (Ed448)
+    ```
+
+    Every certificate conveys an Ed448 key (OID 1.3.101.113), and every
+    signature in the chain is an Ed448 signature.
+    """
+
+    root = builder.root_ca(key=ed448.Ed448PrivateKey.generate())
+    ica = builder.intermediate_ca(root, key=ed448.Ed448PrivateKey.generate())
+    leaf = builder.leaf_cert(ica, key=ed448.Ed448PrivateKey.generate())
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def ed25519_chain_disallowed(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (Ed25519) -> ICA (Ed25519) -> EE (Ed25519)
+    ```
+
+    This is the same chain as `ed25519-chain`, but the acceptable signature
+    algorithms are limited to RSA and ECDSA with SHA-256, excluding EdDSA.
+    """
+
+    root = builder.root_ca(key=ed25519.Ed25519PrivateKey.generate())
+    ica = builder.intermediate_ca(root, key=ed25519.Ed25519PrivateKey.generate())
+    leaf = builder.leaf_cert(ica, key=ed25519.Ed25519PrivateKey.generate())
+
+    builder = (
+        builder.server_validation()
+        .signature_algorithms(
+            [SignatureAlgorithm.RSA_WITH_SHA256, SignatureAlgorithm.ECDSA_WITH_SHA256]
+        )
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def rsa_root_ed25519_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (RSA-2048) -> ICA (Ed25519) -> EE (Ed25519)
+    ```
+
+    The root signs the ICA with RSA, and the ICA signs the EE with Ed25519.
+    """
+
+    root = builder.root_ca(key=rsa.generate_private_key(public_exponent=65537, key_size=2048))
+    ica = builder.intermediate_ca(root, key=ed25519.Ed25519PrivateKey.generate())
+    leaf = builder.leaf_cert(ica, key=ed25519.Ed25519PrivateKey.generate())
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def ed25519_root_rsa_leaf(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (Ed25519) -> EE (RSA-2048)
+    ```
+
+    The root signs the EE with Ed25519, while the EE conveys an RSA key.
+    """
+
+    root = builder.root_ca(key=ed25519.Ed25519PrivateKey.generate())
+    leaf = builder.leaf_cert(
+        root, key=rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def ee_ml_dsa_44_key(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (P-256) -> EE (ML-DSA-44)
+    ```
+
+    The EE conveys an ML-DSA-44 public key. Path validation never uses the
+    EE's key, so validators that recognize ML-DSA should accept this chain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    spki = _spki(_ML_DSA_44, _stub_ml_dsa_44_key())
+    tampered = tamper(leaf, root.key, tbs=replace_tbs_field(TBS_SPKI_INDEX, spki))
+
+    builder = (
+        builder.features([Feature.post_quantum])
+        .client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .expected_peer_name(None)
+        .succeeds()
+    )
+
+
+@testcase
+def ee_ml_kem_768_key(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (P-256) -> EE (ML-KEM-768)
+    ```
+
+    The EE conveys an ML-KEM-768 public key, and its KU asserts only
+    `keyEncipherment`, as is required of ML-KEM keys. Validators that
+    recognize ML-KEM should accept this chain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        key_usage=ext(
+            x509.KeyUsage(
+                digital_signature=False,
+                key_cert_sign=False,
+                content_commitment=False,
+                key_encipherment=True,
+                data_encipherment=False,
+                key_agreement=False,
+                crl_sign=False,
+                encipher_only=False,
+                decipher_only=False,
+            ),
+            critical=True,
+        ),
+    )
+    spki = _spki(_ML_KEM_768, _stub_ml_kem_768_key())
+    tampered = tamper(leaf, root.key, tbs=replace_tbs_field(TBS_SPKI_INDEX, spki))
+
+    builder = (
+        builder.features([Feature.post_quantum])
+        .client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .expected_peer_name(None)
+        .key_usage([KeyUsage.key_encipherment])
+        .succeeds()
+    )
+
+
+def _alt_signature_chain(builder: Builder) -> Builder:
+    """
+    Produces a `root -> EE` chain in which the root conveys an alternative
+    ML-DSA-44 public key, and the EE carries an alternative ML-DSA-44
+    signature that isn't valid under it. All of the alternative key and
+    signature extensions are non-critical.
+    """
+
+    root = builder.root_ca(
+        extra_extension=ext(
+            x509.UnrecognizedExtension(
+                _SUBJECT_ALT_PUBLIC_KEY_INFO, _spki(_ML_DSA_44, _stub_ml_dsa_44_key())
+            ),
+            critical=False,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        extra_unchecked_extensions=[
+            ext(x509.UnrecognizedExtension(_ALT_SIGNATURE_ALGORITHM, _ML_DSA_44), critical=False),
+            ext(
+                x509.UnrecognizedExtension(
+                    _ALT_SIGNATURE_VALUE, der_tlv(0x03, b"\x00" + os.urandom(2420))
+                ),
+                critical=False,
+            ),
+        ],
+    )
+
+    return builder.server_validation().trusted_certs(root).peer_certificate(leaf)
+
+
+@testcase
+def ee_alt_signature_invalid(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (P-256, alt. ML-DSA-44) -> EE
+    ```
+
+    The root's P-256 signature over the EE is valid, but the EE's
+    alternative ML-DSA-44 signature (ITU-T X.509 (2019) 9.8) is not.
+    Validators that verify alternative signatures must reject this chain.
+
+    This is the strict variant of `ee-alt-signature-ignored`.
+    """
+
+    _alt_signature_chain(builder).features([Feature.post_quantum]).conflicts_with(
+        "algorithms::ee-alt-signature-ignored"
+    ).fails()
+
+
+@testcase
+def ee_alt_signature_ignored(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (P-256, alt. ML-DSA-44) -> EE
+    ```
+
+    This is the same as `ee-alt-signature-invalid`, but the alternative key
+    and signature extensions are all non-critical, so validators that don't
+    verify alternative signatures may ignore them and accept this chain.
+    """
+
+    _alt_signature_chain(builder).importance(Importance.LOW).conflicts_with(
+        "algorithms::ee-alt-signature-invalid"
+    ).succeeds()
@@ -0,0 +1,260 @@
+"""
+Code signing profile testcases.
+
+These testcases exercise the requirements that RFC 5280 and the CA/Browser
+Forum's Code Signing Baseline Requirements (CSBR) place on code signing
+end-entity certificates, and on the time-stamping authority (TSA)
+certificates used to countersign code.
+"""
+
+from cryptography import x509
+
+from limbo.assets import CertificatePair, ext
+from limbo.models import CertProfile, KnownEKUs
+from limbo.testcases._core import Builder, testcase
+
+
+def _ku(*, digital_signature: bool = False, content_commitment: bool = False) -> x509.KeyUsage:
+    return x509.KeyUsage(
+        digital_signature=digital_signature,
+        content_commitment=content_commitment,
+        key_encipherment=False,
+        data_encipherment=False,
+        key_agreement=False,
+        key_cert_sign=False,
+        crl_sign=False,
+        encipher_only=False,
+        decipher_only=False,
+    )
+
+
+def _code_signing_leaf(
+    builder: Builder,
+    parent: CertificatePair,
+    *,
+    ekus: list[x509.ObjectIdentifier] | None = None,
+    eku_critical: bool = False,
+    key_usage: x509.KeyUsage | None = None,
+) -> CertificatePair:
+    """
+    Produces a code signing EE certificate, signed by the given `parent`'s
+    key. By default, the certificate conforms to the CSBR.
+    """
+
+    if ekus is None:
+        ekus = [x509.ExtendedKeyUsageOID.CODE_SIGNING]
+
+    if key_usage is None:
+        key_usage = _ku(digital_signature=True)
+
+    return builder.leaf_cert(
+        parent,
+        subject=x509.Name.from_rfc4514_string("CN=Example Software,O=Example Software"),
+        eku=ext(x509.ExtendedKeyUsage(ekus), critical=eku_critical),
+        san=None,
+        key_usage=ext(key_usage, critical=True),
+    )
+
+
+def _validation(
+    builder: Builder,
+    root: CertificatePair,
+    leaf: CertificatePair,
+    *,
+    profile: CertProfile = CertProfile.CODE_SIGNING,
+    eku: KnownEKUs = KnownEKUs.code_signing,
+) -> Builder:
+    return (
+        builder.client_validation()
+        .profile(profile)
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(None)
+        .extended_key_usage([eku])
+    )
+
+
+@testcase
+def ee_code_signing(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is a well-formed code signing certificate: its EKU contains only
+    `codeSigning`, and its critical key usage asserts only
+    `digitalSignature`, per CSBR 7.1.2.3.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(builder, root)
+
+    _validation(builder, root, leaf).succeeds()
+
+
+@testcase
+def ee_content_commitment(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's key usage asserts `contentCommitment` in addition to
+    `digitalSignature`. CSBR 7.1.2.3 say that bits other than
+    `digitalSignature` SHOULD NOT be set, but only forbid `keyCertSign`
+    and `cRLSign`, so a validator must accept this certificate.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(
+        builder, root, key_usage=_ku(digital_signature=True, content_commitment=True)
+    )
+
+    _validation(builder, root, leaf).succeeds()
+
+
+@testcase
+def ee_without_digital_signature(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's key usage asserts only `contentCommitment`. CSBR 7.1.2.3
+    require `digitalSignature` to be set, and RFC 5280 4.2.1.3 requires it
+    for keys that verify signatures other than those on certificates and
+    CRLs, e.g. signatures on code.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(builder, root, key_usage=_ku(content_commitment=True))
+
+    _validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_missing_code_signing_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's EKU contains `emailProtection` rather than `codeSigning`,
+    so the certificate is not valid for code signing under RFC 5280
+    4.2.1.12 or CSBR 7.1.2.3.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(builder, root, ekus=[x509.ExtendedKeyUsageOID.EMAIL_PROTECTION])
+
+    _validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_server_auth(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's EKU contains `serverAuth` alongside `codeSigning`. CSBR
+    7.1.2.3 forbid `serverAuth` in code signing certificates, so that a
+    code signing key can't also be used to authenticate TLS servers.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(
+        builder,
+        root,
+        ekus=[x509.ExtendedKeyUsageOID.CODE_SIGNING, x509.ExtendedKeyUsageOID.SERVER_AUTH],
+    )
+
+    _validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_any_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's EKU contains `anyExtendedKeyUsage` alongside `codeSigning`,
+    which CSBR 7.1.2.3 forbid.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(
+        builder,
+        root,
+        ekus=[
+            x509.ExtendedKeyUsageOID.CODE_SIGNING,
+            x509.ExtendedKeyUsageOID.ANY_EXTENDED_KEY_USAGE,
+        ],
+    )
+
+    _validation(builder, root, leaf).fails()
+
+
+@testcase
+def tsa_ee(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> TSA
+    ```
+
+    The EE is a time-stamping authority certificate of the kind used to
+    countersign code: its EKU is critical and contains only
+    `timeStamping`, as required by RFC 3161 2.3 and CSBR 7.1.2.3.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(
+        builder, root, ekus=[x509.ExtendedKeyUsageOID.TIME_STAMPING], eku_critical=True
+    )
+
+    _validation(
+        builder, root, leaf, profile=CertProfile.TIME_STAMPING, eku=KnownEKUs.time_stamping
+    ).succeeds()
+
+
+@testcase
+def tsa_ee_code_signing(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> TSA
+    ```
+
+    The EE is a time-stamping authority certificate whose critical EKU
+    contains `codeSigning` alongside `timeStamping`. RFC 3161 2.3 requires
+    `timeStamping` to be the only key purpose in a TSA certificate, so that
+    a TSA's key can't also be used to sign code directly.
+    """
+
+    root = builder.root_ca()
+    leaf = _code_signing_leaf(
+        builder,
+        root,
+        ekus=[x509.ExtendedKeyUsageOID.TIME_STAMPING, x509.ExtendedKeyUsageOID.CODE_SIGNING],
+        eku_critical=True,
+    )
+
+    _validation(
+        builder, root, leaf, profile=CertProfile.TIME_STAMPING, eku=KnownEKUs.time_stamping
+    ).fails()
@@ -0,0 +1,131 @@
+"""
+OCSP responder certificate testcases.
+
+These testcases exercise RFC 6960 4.2.2.2's requirements for the
+certificates that sign OCSP responses. Each testcase validates a responder
+certificate as the peer, and the trusted certificate is the CA whose
+certificates' status is being attested: the responder must either be that
+CA, or be a delegated responder issued directly by it.
+"""
+
+from cryptography import x509
+
+from limbo.assets import CertificatePair, ext
+from limbo.models import CertProfile, Feature, KnownEKUs
+from limbo.testcases._core import Builder, testcase
+
+
+def _responder(
+    builder: Builder, parent: CertificatePair, ekus: list[x509.ObjectIdentifier]
+) -> CertificatePair:
+    return builder.leaf_cert(
+        parent,
+        subject=x509.Name.from_rfc4514_string("CN=Example OCSP Responder"),
+        eku=ext(x509.ExtendedKeyUsage(ekus), critical=False),
+        san=None,
+    )
+
+
+def _ocsp_validation(builder: Builder, root: CertificatePair, peer: CertificatePair) -> Builder:
+    return (
+        builder.client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(peer)
+        .expected_peer_name(None)
+        .extended_key_usage([KnownEKUs.ocsp_signing])
+    )
+
+
+@testcase
+def delegated_responder(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> responder
+    ```
+
+    The responder is a delegated OCSP responder: it is issued directly by
+    the CA whose certificates' status it attests, and its EKU contains
+    `OCSPSigning`, as required by RFC 6960 4.2.2.2.
+    """
+
+    root = builder.root_ca()
+    responder = _responder(builder, root, [x509.ExtendedKeyUsageOID.OCSP_SIGNING])
+
+    _ocsp_validation(builder, root, responder).succeeds()
+
+
+@testcase
+def delegated_responder_missing_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> responder
+    ```
+
+    The responder is issued directly by the CA, but its EKU contains
+    `serverAuth` rather than `OCSPSigning`. Per RFC 6960 4.2.2.2, a
+    certificate without `OCSPSigning` is not authorized to sign responses
+    on the CA's behalf.
+    """
+
+    root = builder.root_ca()
+    responder = _responder(builder, root, [x509.ExtendedKeyUsageOID.SERVER_AUTH])
+
+    _ocsp_validation(builder, root, responder).fails()
+
+
+@testcase
+def delegated_responder_issued_by_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> responder
+    ```
+
+    The responder's EKU contains `OCSPSigning`, but it is issued by an
+    intermediate rather than by the CA whose certificates' status it
+    attests. RFC 6960 4.2.2.2 requires a delegated responder's certificate
+    to be issued directly by that CA:
+
+    > Systems relying on OCSP responses MUST recognize a delegation
+    > certificate as being issued by the CA that issued the certificate in
+    > question only if the delegation certificate and the certificate being
+    > checked for revocation were signed by the same key.
+
+    The chain itself is valid, so this requirement is expressed with a
+    maximum chain depth of 0: the responder must be issued directly by the
+    trust anchor, without any intermediates.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    responder = _responder(builder, ica, [x509.ExtendedKeyUsageOID.OCSP_SIGNING])
+
+    _ocsp_validation(builder, root, responder).features(
+        [Feature.max_chain_depth]
+    ).untrusted_intermediates(ica).max_chain_depth(0).fails()
+
+
+@testcase
+def ca_signs_own_responses(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root
+    ```
+
+    The CA signs OCSP responses for its own certificates, so the responder
+    certificate is the trusted CA certificate itself. RFC 6960 4.2.2.2
+    accepts responses signed by the CA that issued the certificate in
+    question without any delegation, so the CA needs no `OCSPSigning` EKU.
+    """
+
+    root = builder.root_ca()
+
+    _ocsp_validation(builder, root, root).succeeds()
@@ -0,0 +1,564 @@
+"""
+Path building testcases, involving multiple trust anchors or multiple
+certificates for the same logical CA.
+"""
+
+from datetime import datetime
+
+from cryptography import x509
+from cryptography.hazmat.primitives.asymmetric import ec
+
+from limbo.models import Feature
+from limbo.testcases._core import Builder, ext, testcase
+
+
+@testcase
+def cross_certified_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root A -> ICA' -+
+                    |-> EE
+    root B -> ICA'' +
+    ```
+
+    `ICA'` and `ICA''` are certificates for the same logical intermediate
+    (same subject and public key), each issued by a different trusted root.
+
+    Both `root A -> ICA' -> EE` and `root B -> ICA'' -> EE` are valid paths.
+    """
+    root_a = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a"))
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b"))
+
+    ica_key = ec.generate_private_key(ec.SECP256R1())
+    ica_subject = x509.Name.from_rfc4514_string("CN=x509-limbo-cross-certified-intermediate")
+    ica_a = builder.intermediate_ca(root_a, subject=ica_subject, key=ica_key)
+    ica_b = builder.intermediate_ca(root_b, subject=ica_subject, key=ica_key)
+
+    leaf = builder.leaf_cert(ica_a)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_b)
+        .untrusted_intermediates(ica_a, ica_b)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def cross_certified_intermediate_expired_root(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root A (expired) -> ICA' -+
+                              |-> EE
+    root B           -> ICA'' +
+    ```
+
+    `ICA'` and `ICA''` are certificates for the same logical intermediate
+    (same subject and public key), each issued by a different trusted root.
+    Root A is expired at the validation time, so `root A -> ICA' -> EE` is
+    not a valid path.
+
+    A path builder should discard the path through root A and successfully
+    build `root B -> ICA'' -> EE`.
+    """
+    root_a = builder.root_ca(
+        issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a"),
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2020-01-01T00:00:00Z"),
+    )
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b"))
+
+    ica_key = ec.generate_private_key(ec.SECP256R1())
+    ica_subject = x509.Name.from_rfc4514_string("CN=x509-limbo-cross-certified-intermediate")
+    ica_a = builder.intermediate_ca(root_a, subject=ica_subject, key=ica_key)
+    ica_b = builder.intermediate_ca(root_b, subject=ica_subject, key=ica_key)
+
+    leaf = builder.leaf_cert(ica_a)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_b)
+        .untrusted_intermediates(ica_a, ica_b)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is after root A's expiry.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .succeeds()
+    )
+
+
+@testcase
+def cross_certified_intermediate_single_cert(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root A -> [ICA'] -+
+                      |-> EE
+    root B -> ICA''  -+
+    ```
+
+    This is the same as `cross-certified-intermediate`, except that only
+    `ICA''` is supplied as an untrusted intermediate. The EE was issued
+    under `ICA'`, but since both certificates share a subject and public
+    key, `root B -> ICA'' -> EE` is a valid path.
+    """
+    root_a = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a"))
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b"))
+
+    ica_key = ec.generate_private_key(ec.SECP256R1())
+    ica_subject = x509.Name.from_rfc4514_string("CN=x509-limbo-cross-certified-intermediate")
+    ica_a = builder.intermediate_ca(root_a, subject=ica_subject, key=ica_key)
+    ica_b = builder.intermediate_ca(root_b, subject=ica_subject, key=ica_key)
+
+    leaf = builder.leaf_cert(ica_a)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_b)
+        .untrusted_intermediates(ica_b)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def cross_certified_intermediate_single_cert_expired_root(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root A (expired) -> [ICA'] -+
+                                |-> EE
+    root B           -> ICA''  -+
+    ```
+
+    This is the same as `cross-certified-intermediate-expired-root`, except
+    that only `ICA''` is supplied as an untrusted intermediate. The EE was
+    issued under `ICA'`, but the only path that can be built is the valid
+    path through the unexpired root B: `root B -> ICA'' -> EE`.
+    """
+    root_a = builder.root_ca(
+        issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a"),
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2020-01-01T00:00:00Z"),
+    )
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b"))
+
+    ica_key = ec.generate_private_key(ec.SECP256R1())
+    ica_subject = x509.Name.from_rfc4514_string("CN=x509-limbo-cross-certified-intermediate")
+    ica_a = builder.intermediate_ca(root_a, subject=ica_subject, key=ica_key)
+    ica_b = builder.intermediate_ca(root_b, subject=ica_subject, key=ica_key)
+
+    leaf = builder.leaf_cert(ica_a)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_b)
+        .untrusted_intermediates(ica_b)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is after root A's expiry.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .succeeds()
+    )
+
+
+@testcase
+def multiple_roots_chain_to_first(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root A -> ICA -> EE
+    root B
+    ```
+
+    Both roots are trust anchors with different subjects and keys, but
+    only root A issued the intermediate. The path builder must select
+    root A as the trust anchor.
+    """
+    root_a = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a"))
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b"))
+    intermediate = builder.intermediate_ca(root_a)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_b)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def multiple_roots_wrong_signer(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root A
+    root B -> ICA -> EE
+    ```
+
+    Both roots are trust anchors with different subjects and keys. The
+    intermediate's issuer and AKI identify root B, but it was actually
+    signed by root A's key. Root B's key doesn't verify the intermediate's
+    signature, and root A isn't named as its issuer, so there is no valid
+    path.
+    """
+    root_a = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a"))
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b"))
+    intermediate = builder.intermediate_ca(
+        root_a,
+        issuer=root_b.cert.subject,
+        aki=ext(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(root_b.key.public_key()),
+            critical=False,
+        ),
+    )
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_b)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def multiple_roots_same_key(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root -+
+          |-> ICA -> EE
+    root' +
+    ```
+
+    `root` and `root'` are distinct trust anchors for the same logical CA
+    (same subject and public key, different serial numbers and validity
+    periods), such as a root that has been reissued. Each verifies the
+    intermediate's signature, so both `root -> ICA -> EE` and
+    `root' -> ICA -> EE` are valid paths.
+    """
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    root_subject = x509.Name.from_rfc4514_string("CN=x509-limbo-reissued-root")
+    root = builder.root_ca(issuer=root_subject, key=root_key)
+    reissued_root = builder.root_ca(
+        issuer=root_subject,
+        key=root_key,
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+    )
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root, reissued_root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def bridge_ca(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root A -> bridge (root B') -> ICA B -> EE
+    ```
+
+    Root A and root C belong to different organizations and are both trusted.
+    Root B's hierarchy is otherwise disjoint from root A's, but root A has
+    cross-certified root B's subject and key as a bridge CA. The leaf is
+    issued by an intermediate under root B, and root B's own self-signed
+    certificate is not trusted.
+
+    The only valid path is through the bridge: `root A -> root B' -> ICA B -> EE`.
+    """
+    root_a = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a,O=Org A"))
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b,O=Org B"))
+    root_c = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-c,O=Org C"))
+
+    bridge = builder.intermediate_ca(root_a, subject=root_b.cert.subject, key=root_b.key)
+    ica_b = builder.intermediate_ca(root_b)
+    leaf = builder.leaf_cert(ica_b)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_c)
+        .untrusted_intermediates(bridge, ica_b)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def bridge_ca_expired(builder: Builder) -> None:
+    """
+    Produces the following **invalid** graph:
+
+    ```
+    root A -> bridge (root B', expired) -> ICA B -> EE
+    ```
+
+    This is the same as `bridge-ca`, except that the bridge CA certificate
+    is expired at the validation time. Since the bridge is the only link
+    between root B's hierarchy and a trust anchor, no valid path exists.
+    """
+    root_a = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-a,O=Org A"))
+    root_b = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-b,O=Org B"))
+    root_c = builder.root_ca(issuer=x509.Name.from_rfc4514_string("CN=x509-limbo-root-c,O=Org C"))
+
+    bridge = builder.intermediate_ca(
+        root_a,
+        subject=root_b.cert.subject,
+        key=root_b.key,
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2020-01-01T00:00:00Z"),
+    )
+    ica_b = builder.intermediate_ca(root_b)
+    leaf = builder.leaf_cert(ica_b)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_c)
+        .untrusted_intermediates(bridge, ica_b)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is after the bridge's expiry.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .fails()
+    )
+
+
+@testcase
+def key_rollover_root_new_with_old(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root (old key) -> root' (new key) -> ICA -> EE
+    ```
+
+    This models a root key rollover: the root has rolled over to a new key
+    pair under the same subject, and the old key has signed a "NewWithOld"
+    certificate for the new key to maintain continuity. Only the old root
+    is a trust anchor, while the intermediate and leaf are issued under the
+    new key.
+
+    `root'` is self-issued (but not self-signed), and its AKI references
+    the old root's key.
+    """
+    root = builder.root_ca()
+    new_key = ec.generate_private_key(ec.SECP256R1())
+    new_with_old = builder.intermediate_ca(root, subject=root.cert.subject, key=new_key)
+    intermediate = builder.intermediate_ca(new_with_old)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(new_with_old, intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def key_rollover_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root -> ICA (old key) -> ICA' (new key) -> EE
+    ```
+
+    This models an intermediate key rollover: the intermediate has rolled
+    over to a new key pair under the same subject, and its old key has
+    signed a certificate for the new key. The leaf is issued under the
+    new key.
+
+    Both intermediate certificates are present in the untrusted
+    intermediate set, so the path builder can bridge from the new key
+    back to the trust anchor.
+    """
+    root = builder.root_ca()
+    old = builder.intermediate_ca(root)
+    new_key = ec.generate_private_key(ec.SECP256R1())
+    new_with_old = builder.intermediate_ca(old, subject=old.cert.subject, key=new_key)
+    leaf = builder.leaf_cert(new_with_old)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(old, new_with_old)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def key_rollover_intermediate_missing_old(builder: Builder) -> None:
+    """
+    Produces the following **invalid** graph:
+
+    ```
+    root -> [missing ICA (old key)] -> ICA' (new key) -> EE
+    ```
+
+    This is the same as `key-rollover-intermediate`, except that only the
+    new intermediate certificate is present. `ICA'` was signed by the
+    intermediate's old key (as its AKI indicates), and without the old
+    intermediate certificate there is no path back to the trust anchor.
+    """
+    root = builder.root_ca()
+    old = builder.intermediate_ca(root)
+    new_key = ec.generate_private_key(ec.SECP256R1())
+    new_with_old = builder.intermediate_ca(old, subject=old.cert.subject, key=new_key)
+    leaf = builder.leaf_cert(new_with_old)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(new_with_old)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def direct_ee(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is issued directly by the trust anchor, and no untrusted
+    intermediates are supplied. The EE's issuer and AKI both match the
+    root's subject and SKI, so the path is a single hop from the EE to
+    the trust anchor.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    builder = builder.server_validation().trusted_certs(root).peer_certificate(leaf).succeeds()
+
+
+@testcase
+def direct_ee_unknown_issuer(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> [missing ICA] -> EE
+    ```
+
+    The EE is issued by an intermediate that isn't supplied, so its issuer
+    and AKI don't match the trust anchor or any other certificate. A path
+    builder that only considers the single hop from the EE to the trust
+    anchor must not accept the trust anchor as the EE's issuer.
+    """
+    root = builder.root_ca()
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = builder.server_validation().trusted_certs(root).peer_certificate(leaf).fails()
+
+
+@testcase
+def direct_ee_aki_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is issued and signed directly by the trust anchor, and its issuer
+    matches the root's subject, but its AKI's `keyIdentifier` doesn't match
+    the root's SKI, as required by RFC 5280 4.2.1.2:
+
+    > The value of the subject key identifier MUST be the value placed in
+    > the key identifier field of the authority key identifier extension
+    > (Section 4.2.1.1) of certificates issued by the subject of this
+    > certificate.
+
+    Validators that use the AKI only as a hint for path building (and rely
+    on the signature instead) will accept this chain.
+    """
+    root = builder.root_ca()
+    throwaway_key = ec.generate_private_key(ec.SECP256R1())
+    leaf = builder.leaf_cert(
+        root,
+        aki=ext(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(throwaway_key.public_key()),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.pedantic_rfc5280])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def no_trust_anchors_self_signed_ee(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    EE
+    ```
+
+    The EE is self-signed, and no trust anchors are supplied. Without a
+    trust anchor there is no root of trust, so no path can be valid.
+    """
+    key = ec.generate_private_key(ec.SECP256R1())
+    name = x509.Name.from_rfc4514_string("CN=example.com")
+    # NOTE: Only used to sign the EE with its own key; never supplied.
+    signer = builder.root_ca(issuer=name, key=key)
+    leaf = builder.leaf_cert(signer, subject=name, key=key)
+
+    builder = builder.server_validation().trusted_certs().peer_certificate(leaf).fails()
+
+
+@testcase
+def no_trust_anchors_untrusted_issuers(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    [root] -> [ICA] -> EE
+    ```
+
+    The EE's issuing ICA and that ICA's root are both supplied, but only as
+    untrusted intermediates, and no trust anchors are supplied. A complete
+    chain to a self-signed root is not a substitute for a trust anchor.
+    """
+    root = builder.root_ca()
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs()
+        .untrusted_intermediates(intermediate, root)
+        .peer_certificate(leaf)
+        .fails()
+    )
@@ -2,8 +2,10 @@
 Testcases for path length constraints, as well as chain depth constraints.
 """
 
-from limbo.models import Feature
-from limbo.testcases._core import Builder, testcase
+from cryptography import x509
+
+from limbo.models import Feature, Importance
+from limbo.testcases._core import Builder, ext, testcase
 
 
 @testcase
@@ -116,6 +118,249 @@ def validation_ignores_pathlen_in_leaf(builder: Builder) -> None:
     )
 
 
+@testcase
+def ee_with_pathlen_0(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> intermediate -> EE (pathlen:0)
+    ```
+
+    The EE carries a `basicConstraints` extension with `cA:FALSE` and a
+    `pathLenConstraint` of 0. RFC 5280 4.2.1.9 says CAs MUST NOT include
+    a `pathLenConstraint` unless `cA` is asserted, but the constraint is
+    only meaningful for CA certificates. Since the EE is the last
+    certificate in the path, the constraint should be ignored.
+    """
+    root = builder.root_ca()
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(
+        intermediate,
+        basic_constraints=None,
+        # SEQUENCE { INTEGER 0 }, i.e. cA:FALSE (DEFAULT, omitted) with pathLen:0
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.OID_BASIC_CONSTRAINTS, b"\x30\x03\x02\x01\x00"),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def root_pathlen_0_direct_ee(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (pathlen:0) -> EE
+    ```
+
+    The trust anchor's `pathlen:0` forbids intermediate CAs beneath it, but
+    still permits it to issue end-entity certificates directly, since
+    RFC 5280 4.2.1.9's path length only counts non-self-issued
+    intermediates.
+    """
+    root = builder.root_ca(
+        basic_constraints=ext(x509.BasicConstraints(ca=True, path_length=0), critical=True)
+    )
+    leaf = builder.leaf_cert(root)
+
+    builder = builder.server_validation()
+    builder = builder.trusted_certs(root).peer_certificate(leaf).succeeds()
+
+
+@testcase
+def root_pathlen_0_with_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (pathlen:0) -> intermediate -> EE
+    ```
+
+    The trust anchor's `pathlen:0` forbids any intermediate CAs beneath it,
+    so this chain violates the root's path length constraint.
+
+    RFC 5280 6.1 treats the trust anchor as an input to path validation
+    rather than part of the path, so strictly speaking its constraints are
+    not processed. In practice, many implementations enforce constraints on
+    self-signed trust anchors.
+
+    This is the strict variant of `root-pathlen-0-with-intermediate-trusted`.
+    """
+    root = builder.root_ca(
+        basic_constraints=ext(x509.BasicConstraints(ca=True, path_length=0), critical=True)
+    )
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = builder.server_validation().features([Feature.strict_anchor_constraints])
+    builder = (
+        builder.conflicts_with("pathlen::root-pathlen-0-with-intermediate-trusted")
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def root_pathlen_0_with_intermediate_trusted(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (pathlen:0) -> intermediate -> EE
+    ```
+
+    This is the same as `root-pathlen-0-with-intermediate`, but the relying
+    party doesn't process the trust anchor's constraints, as RFC 5280 6.1
+    permits.
+    """
+    root = builder.root_ca(
+        basic_constraints=ext(x509.BasicConstraints(ca=True, path_length=0), critical=True)
+    )
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = builder.server_validation().importance(Importance.UNDETERMINED)
+    builder = (
+        builder.conflicts_with("pathlen::root-pathlen-0-with-intermediate")
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def root_pathlen_1_with_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (pathlen:1) -> intermediate -> EE
+    ```
+
+    The trust anchor's `pathlen:1` permits exactly one intermediate CA
+    beneath it.
+    """
+    root = builder.root_ca(
+        basic_constraints=ext(x509.BasicConstraints(ca=True, path_length=1), critical=True)
+    )
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def root_pathlen_1_with_two_intermediates(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (pathlen:1) -> intermediate -> intermediate -> EE
+    ```
+
+    The trust anchor's `pathlen:1` permits only one intermediate CA beneath
+    it, so this chain violates the root's path length constraint.
+
+    See `root-pathlen-0-with-intermediate` for a note on RFC 5280's
+    treatment of trust anchor constraints.
+
+    This is the strict variant of `root-pathlen-1-with-two-intermediates-trusted`.
+    """
+    root = builder.root_ca(
+        basic_constraints=ext(x509.BasicConstraints(ca=True, path_length=1), critical=True)
+    )
+    first_intermediate = builder.intermediate_ca(root)
+    second_intermediate = builder.intermediate_ca(first_intermediate)
+    leaf = builder.leaf_cert(second_intermediate)
+
+    builder = builder.server_validation().features([Feature.strict_anchor_constraints])
+    builder = (
+        builder.conflicts_with("pathlen::root-pathlen-1-with-two-intermediates-trusted")
+        .trusted_certs(root)
+        .untrusted_intermediates(first_intermediate, second_intermediate)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def root_pathlen_1_with_two_intermediates_trusted(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (pathlen:1) -> intermediate -> intermediate -> EE
+    ```
+
+    This is the same as `root-pathlen-1-with-two-intermediates`, but the
+    relying party doesn't process the trust anchor's constraints, as
+    RFC 5280 6.1 permits.
+    """
+    root = builder.root_ca(
+        basic_constraints=ext(x509.BasicConstraints(ca=True, path_length=1), critical=True)
+    )
+    first_intermediate = builder.intermediate_ca(root)
+    second_intermediate = builder.intermediate_ca(first_intermediate)
+    leaf = builder.leaf_cert(second_intermediate)
+
+    builder = builder.server_validation().importance(Importance.UNDETERMINED)
+    builder = (
+        builder.conflicts_with("pathlen::root-pathlen-1-with-two-intermediates")
+        .trusted_certs(root)
+        .untrusted_intermediates(first_intermediate, second_intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def intermediate_pathlen_exceeds_chain(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> intermediate (pathlen:2) -> intermediate -> EE
+    ```
+
+    The first intermediate's `pathlen:2` permits up to two intermediate CAs
+    beneath it, but only one is present. A constraint larger than the
+    remaining chain length is satisfied; implementations that compare in
+    the wrong direction will reject this chain.
+    """
+
+    root = builder.root_ca()
+    first_intermediate = builder.intermediate_ca(root, pathlen=2)
+    second_intermediate = builder.intermediate_ca(first_intermediate)
+    leaf = builder.leaf_cert(second_intermediate)
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .untrusted_intermediates(first_intermediate, second_intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
 @testcase
 def intermediate_violates_pathlen_0(builder: Builder) -> None:
     """
@@ -295,6 +295,221 @@ def intermediate_cycle_same_logical_ca(builder: Builder) -> None:
     )
 
 
+def _intermediate_cycle(n: int) -> list[CertificatePair]:
+    """
+    Produces `n` distinct logical CAs, `ICA 0` through `ICA n-1`, where each
+    `ICA i` is issued by `ICA i+1` and `ICA n-1` is issued by `ICA 0`.
+    """
+
+    basic_constraints = x509.BasicConstraints(ca=True, path_length=None)
+    key_usage = x509.KeyUsage(
+        digital_signature=False,
+        key_cert_sign=True,
+        content_commitment=False,
+        key_encipherment=False,
+        data_encipherment=False,
+        key_agreement=False,
+        crl_sign=False,
+        encipher_only=False,
+        decipher_only=False,
+    )
+
+    keys = [ec.generate_private_key(ec.SECP256R1()) for _ in range(n)]
+    names = [x509.Name.from_rfc4514_string(f"CN=intermediate-cycle-{n}-ca{i}") for i in range(n)]
+
+    # NOTE: Uses CertificateBuilder directly to sidestep the certificate dep cycle.
+    pairs = []
+    for i in range(n):
+        issuer = (i + 1) % n
+        cert = (
+            x509.CertificateBuilder()
+            .serial_number(x509.random_serial_number())
+            .not_valid_before(EPOCH)
+            .not_valid_after(ONE_THOUSAND_YEARS_OF_TORMENT)
+            .issuer_name(names[issuer])
+            .subject_name(names[i])
+            .add_extension(basic_constraints, critical=True)
+            .add_extension(key_usage, critical=False)
+            .add_extension(
+                x509.AuthorityKeyIdentifier.from_issuer_public_key(keys[issuer].public_key()),
+                critical=False,
+            )
+            .add_extension(
+                x509.SubjectKeyIdentifier.from_public_key(keys[i].public_key()), critical=False
+            )
+            .public_key(keys[i].public_key())
+        ).sign(keys[issuer], algorithm=hashes.SHA256())
+        pairs.append(CertificatePair(cert, keys[i]))
+
+    # Sanity check
+    for i in range(n):
+        pairs[i].cert.verify_directly_issued_by(pairs[(i + 1) % n].cert)
+
+    return pairs
+
+
+@testcase
+def intermediate_cycle_3_cas(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -/-> (ICA 0 <- ICA 1 <- ICA 2 <- ICA 0) -> EE
+    ```
+
+    The three ICAs are separate logical CAs that form an issuance cycle.
+    None of them chains up to the root, so path building must terminate
+    without finding a path.
+    """
+
+    root = builder.root_ca()
+    icas = _intermediate_cycle(3)
+
+    leaf = builder.leaf_cert(icas[0])
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(*icas)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def intermediate_cycle_5_cas(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -/-> (ICA 0 <- ICA 1 <- ... <- ICA 4 <- ICA 0) -> EE
+    ```
+
+    This is the same as `intermediate-cycle-3-cas`, except that the cycle
+    is five CAs long.
+    """
+
+    root = builder.root_ca()
+    icas = _intermediate_cycle(5)
+
+    leaf = builder.leaf_cert(icas[0])
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(*icas)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def intermediate_cycle_trusted_member(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    ICA 2 (trusted) -> ICA 1 -> ICA 0 -> EE
+    ```
+
+    This is the same as `intermediate-cycle-3-cas`, except that `ICA 2` is
+    also a trust anchor. The trust anchor breaks the cycle, so
+    `ICA 2 -> ICA 1 -> ICA 0 -> EE` is a valid path.
+    """
+
+    icas = _intermediate_cycle(3)
+
+    leaf = builder.leaf_cert(icas[0])
+    builder = (
+        builder.server_validation()
+        .trusted_certs(icas[2])
+        .untrusted_intermediates(*icas)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+def _large_cert_store(builder: Builder, n: int) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    along with `n` other intermediates issued by the root, each with a
+    distinct subject. Only `ICA` issued the EE.
+    """
+
+    root = builder.root_ca()
+
+    # NOTE: The decoys share a key, since generating thousands of keys
+    # would make generation needlessly slow.
+    decoy_key = ec.generate_private_key(ec.SECP256R1())
+    decoys = [
+        builder.intermediate_ca(
+            root,
+            subject=x509.Name.from_rfc4514_string(f"CN=x509-limbo-large-store-decoy-{i}"),
+            key=decoy_key,
+        )
+        for i in range(n)
+    ]
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.large_store])
+        .trusted_certs(root)
+        .untrusted_intermediates(*decoys[: n // 2], intermediate, *decoys[n // 2 :])
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def large_cert_store_100(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The untrusted intermediates contain `ICA` along with 100 other
+    intermediates that don't lead to the EE.
+    """
+    _large_cert_store(builder, 100)
+
+
+@testcase
+def large_cert_store_1000(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The untrusted intermediates contain `ICA` along with 1000 other
+    intermediates that don't lead to the EE.
+    """
+    _large_cert_store(builder, 1000)
+
+
+@testcase
+def large_cert_store_5000(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The untrusted intermediates contain `ICA` along with 5000 other
+    intermediates that don't lead to the EE.
+    """
+    _large_cert_store(builder, 5000)
+
+
 @testcase
 def nc_dos_1(builder: Builder) -> None:
     """
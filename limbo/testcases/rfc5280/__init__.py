@@ -5,18 +5,25 @@ RFC5280 profile tests.
 from datetime import datetime
 
 from cryptography import x509
+from cryptography.hazmat.primitives.asymmetric import ec
 
-from limbo.assets import ASSETS_PATH, Certificate, ext
-from limbo.models import PeerName
+from limbo.assets import ASSETS_PATH, Certificate, ext, tamper
+from limbo.models import Feature, Importance, PeerName
 from limbo.testcases._core import Builder, testcase
 
 from .aki import *  # noqa: F403
+from .cp import *  # noqa: F403
+from .der import *  # noqa: F403
 from .eku import *  # noqa: F403
+from .ku import *  # noqa: F403
+from .name_comparison import *  # noqa: F403
 from .nc import *  # noqa: F403
 from .pc import *  # noqa: F403
+from .pm import *  # noqa: F403
 from .san import *  # noqa: F403
 from .serial import *  # noqa: F403
 from .ski import *  # noqa: F403
+from .uid import *  # noqa: F403
 from .validity import *  # noqa: F403
 
 
@@ -149,6 +156,72 @@ def unknown_critical_extension_intermediate(builder: Builder) -> None:
     )
 
 
+@testcase
+def unknown_noncritical_extension_ee(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE cert has an extension, 1.3.6.1.4.1.55738.666.1, that no implementation
+    should recognize. Unlike in `unknown-critical-extension-ee`, this extension
+    is not marked as critical, so RFC 5280 4.2 requires that it be ignored:
+
+    > A certificate-using system MUST reject the certificate if it encounters
+    > a critical extension it does not recognize or a critical extension
+    > that contains information that it cannot process. A non-critical
+    > extension MAY be ignored if it is not recognized, but MUST be
+    > processed if it is recognized.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.ObjectIdentifier("1.3.6.1.4.1.55738.666.1"), b""),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = builder.trusted_certs(root).peer_certificate(leaf).succeeds()
+
+
+@testcase
+def unknown_noncritical_extension_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> intermediate (pathlen:0) -> EE
+    ```
+
+    This is the same as `unknown-critical-extension-intermediate`, except that
+    the intermediate's unrecognized extension is not marked as critical, and
+    so must be ignored.
+    """
+
+    root = builder.root_ca()
+    intermediate = builder.intermediate_ca(
+        root,
+        pathlen=0,
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.ObjectIdentifier("1.3.6.1.4.1.55738.666.1"), b""),
+            critical=False,
+        ),
+    )
+    leaf = builder.leaf_cert(intermediate)
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
 @testcase
 def chain_untrusted_root(builder: Builder) -> None:
     """
@@ -244,14 +317,40 @@ def root_missing_basic_constraints(builder: Builder) -> None:
     > that contain public keys used to validate digital signatures on
     > certificates and MUST mark the extension as critical in such
     > certificates.
+
+    This is the strict variant of `root-missing-basic-constraints-trusted`.
     """
     root = builder.root_ca(basic_constraints=None)
     leaf = builder.leaf_cert(root)
 
-    builder = builder.server_validation()
+    builder = builder.server_validation().features([Feature.strict_anchor_constraints])
     builder.trusted_certs(root).peer_certificate(leaf).fails()
 
 
+@testcase
+def root_missing_basic_constraints_trusted(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    This is the same as `root-missing-basic-constraints`, but the relying
+    party trusts the root unconditionally.
+
+    Older trust anchors sometimes lack a BasicConstraints extension entirely.
+    RFC 5937 4 permits relying parties to use only a trust anchor's name
+    and public key, and many validators (such as webpki) never examine a
+    trust anchor's extensions.
+    """
+    root = builder.root_ca(basic_constraints=None)
+    leaf = builder.leaf_cert(root)
+
+    builder = builder.server_validation().importance(Importance.UNDETERMINED)
+    builder.trusted_certs(root).peer_certificate(leaf).succeeds()
+
+
 @testcase
 def root_non_critical_basic_constraints(builder: Builder) -> None:
     """
@@ -652,3 +751,69 @@ def root_and_intermediate_swapped(builder: Builder) -> None:
         .expected_peer_name(PeerName(kind="DNS", value="example.com"))
         .succeeds()
     )
+
+
+@testcase
+def self_signed_not_self_issued_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (self-signed) -> EE
+    ```
+
+    The ICA's issuer is the root's subject, so it is not self-issued
+    (RFC 5280 6.1: subject and issuer differ). However, it is signed by its
+    own key rather than the root's, so it is self-signed in the
+    cryptographic sense.
+
+    Self-signedness and self-issuance are independent properties. Here the
+    issuer name leads to the root, but the root's key does not verify the
+    ICA's signature, so the chain is broken at the ICA.
+    """
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    self_signed_ica = tamper(ica, ica.key)
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(self_signed_ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def self_issued_not_self_signed_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (self-issued) -> EE
+    ```
+
+    The ICA has the same subject and issuer as the root, making it
+    self-issued, but with a different key. Its signature does not verify with
+    its own key, nor with the root's key: it was produced by an unrelated key.
+
+    This is the same as `pathbuilding::key-rollover-root-new-with-old`,
+    except that the self-issued certificate's signature is bad. A matching
+    subject and issuer is not evidence of a valid signature, so this
+    chain should be rejected as a signature verification failure.
+    """
+    root = builder.root_ca()
+    ica_key = ec.generate_private_key(ec.SECP256R1())
+    ica = builder.intermediate_ca(root, subject=root.cert.subject, key=ica_key)
+    unrelated_key = ec.generate_private_key(ec.SECP256R1())
+    badly_signed_ica = tamper(ica, unrelated_key)
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(badly_signed_ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
@@ -131,3 +131,25 @@ def cross_signed_root_missing_aki(builder: Builder) -> None:
 
     builder = builder.server_validation().features([Feature.pedantic_rfc5280])
     builder.trusted_certs(root).peer_certificate(leaf).fails()
+
+
+@testcase
+def cross_signed_root_with_aki(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The root is cross signed by another root and carries an AKI extension
+    identifying its signer, as RFC 5280 4.2.1.1 requires of certificates
+    that aren't self-signed. This is the conforming counterpart of
+    `cross-signed-root-missing-aki`.
+    """
+    xsigner_root = builder.root_ca()
+    root = builder.intermediate_ca(xsigner_root, pathlen=0)
+    leaf = builder.leaf_cert(root)
+
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).succeeds()
@@ -0,0 +1,137 @@
+"""
+RFC 5280 Certificate Policies (CP) testcases.
+
+RFC 5280 4.2.1.4 allows applications to ignore policy qualifiers, and most
+do: as of writing, `rust-webpki`, `rustls-webpki`, `pyca-cryptography`, and
+OpenSSL (with or without `-policy_check`) all accept certificates whose
+qualifiers are malformed. These implementations only inspect the policy
+identifiers, if anything.
+"""
+
+from cryptography import x509
+
+from limbo.assets import der_tlv, ext
+from limbo.models import Feature
+from limbo.testcases._core import Builder, testcase
+
+# An arbitrary certificate policy, under x509-limbo's PEN.
+_TEST_POLICY = x509.ObjectIdentifier("1.3.6.1.4.1.55738.666.2")
+
+# `_TEST_POLICY`, DER-encoded.
+_TEST_POLICY_DER = bytes.fromhex("060b2b0601040183b33a851a02")
+
+# id-qt-cps (1.3.6.1.5.5.7.2.1), DER-encoded.
+_CPS_QUALIFIER_OID = bytes.fromhex("06082b06010505070201")
+
+# id-qt-unotice (1.3.6.1.5.5.7.2.2), DER-encoded.
+_USER_NOTICE_QUALIFIER_OID = bytes.fromhex("06082b06010505070202")
+
+
+def _policies_with_qualifier(qualifier_id: bytes, qualifier: bytes) -> x509.UnrecognizedExtension:
+    """
+    Returns a `certificatePolicies` extension containing `_TEST_POLICY`
+    with a single, pre-encoded policy qualifier.
+
+    This bypasses `cryptography`'s encoder, which only produces
+    well-formed CPS URIs and UTF8String explicit texts.
+    """
+    qualifier_info = der_tlv(0x30, qualifier_id + qualifier)
+    policy_information = der_tlv(0x30, _TEST_POLICY_DER + der_tlv(0x30, qualifier_info))
+    return x509.UnrecognizedExtension(
+        x509.OID_CERTIFICATE_POLICIES, der_tlv(0x30, policy_information)
+    )
+
+
+@testcase
+def ee_cps_uri_qualifier(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's `certificatePolicies` extension asserts a policy with a
+    CPS pointer qualifier containing an HTTP URI, as permitted by
+    RFC 5280 4.2.1.4.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        extra_extension=ext(
+            x509.CertificatePolicies(
+                [x509.PolicyInformation(_TEST_POLICY, ["http://example.com/cps"])]
+            ),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation().trusted_certs(root).peer_certificate(leaf).succeeds()
+
+
+@testcase
+def ee_cps_uri_not_ia5string(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's `certificatePolicies` extension asserts a policy with a
+    CPS pointer qualifier whose URI contains a non-ASCII byte (`0xE4`).
+    RFC 5280 4.2.1.4 defines the CPS pointer as an `IA5String`, which
+    can only contain ASCII, so the extension is malformed.
+
+    Many implementations never parse policy qualifiers, since RFC 5280
+    4.2.1.4 says that applications may ignore them; those implementations
+    will incorrectly accept this certificate.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        extra_extension=ext(
+            _policies_with_qualifier(
+                _CPS_QUALIFIER_OID, der_tlv(0x16, b"http://ex\xe4mple.com/cps")
+            ),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.pedantic_rfc5280])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ee_user_notice_bmpstring(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's `certificatePolicies` extension asserts a policy with a
+    user notice qualifier whose `explicitText` is a `BMPString`.
+    RFC 5280 4.2.1.4 (as amended by RFC 6818) prefers `UTF8String`, but
+    says that `BMPString` is an acceptable alternative.
+    """
+
+    root = builder.root_ca()
+    user_notice = der_tlv(0x30, der_tlv(0x1E, "Example notice".encode("utf-16-be")))
+    leaf = builder.leaf_cert(
+        root,
+        extra_extension=ext(
+            _policies_with_qualifier(_USER_NOTICE_QUALIFIER_OID, user_notice),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation().trusted_certs(root).peer_certificate(leaf).succeeds()
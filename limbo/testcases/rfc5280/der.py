@@ -0,0 +1,259 @@
+"""
+RFC 5280 DER encoding testcases.
+"""
+
+from cryptography import x509
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric import rsa
+
+from limbo.assets import (
+    TBS_SPKI_INDEX,
+    TBS_SUBJECT_INDEX,
+    RawCertificate,
+    der_children,
+    der_tlv,
+    ext,
+    replace_tbs_field,
+    tamper,
+)
+from limbo.models import Feature
+from limbo.testcases._core import Builder, testcase
+
+
+@testcase
+def ber_boolean(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA's `basicConstraints` encodes `cA` as the BOOLEAN `01 01 01`.
+    Under BER any non-zero octet is `TRUE`, but DER (X.690 11.1) requires
+    `TRUE` to be encoded as `0xFF`. RFC 5280 1 requires certificates to be
+    DER-encoded, so strict implementations should reject this chain.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        basic_constraints=None,
+        # SEQUENCE { BOOLEAN 0x01 }
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.OID_BASIC_CONSTRAINTS, b"\x30\x03\x01\x01\x01"),
+            critical=True,
+        ),
+    )
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.strict_der])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ber_set_order(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's subject is a single multi-valued RDN, whose `SET OF`
+    attributes are not in the canonical order required by DER
+    (X.690 11.6). RFC 5280 1 requires certificates to be DER-encoded,
+    so strict implementations should reject this chain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    # Under DER, `SET OF` elements are sorted by their complete encodings,
+    # so the shorter O attribute (`30 11 ...`) sorts before the CN attribute
+    # (`30 12 ...`); we deliberately encode them in the opposite order.
+    attributes = [
+        der_tlv(0x30, bytes.fromhex("0603550403") + der_tlv(0x0C, b"example.com")),
+        der_tlv(0x30, bytes.fromhex("060355040a") + der_tlv(0x0C, b"x509-limbo")),
+    ]
+    assert attributes != sorted(attributes)
+    subject = der_tlv(0x30, der_tlv(0x31, b"".join(attributes)))
+
+    tampered = tamper(leaf, root.key, tbs=replace_tbs_field(TBS_SUBJECT_INDEX, subject))
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.strict_der])
+        .conflicts_with("rfc5280::name-comparison::multi-value-rdn")
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .fails()
+    )
+
+
+@testcase
+def ber_indefinite_length(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's outermost `Certificate` SEQUENCE uses the BER indefinite-length
+    form (`30 80 ... 00 00`), which DER (X.690 10.1) forbids. The signed
+    `tbsCertificate` is otherwise unmodified, so its signature is valid.
+    RFC 5280 1 requires certificates to be DER-encoded, so strict
+    implementations should reject this chain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    der = leaf.cert.public_bytes(serialization.Encoding.DER)
+    indefinite = RawCertificate(b"\x30\x80" + b"".join(der_children(der)) + b"\x00\x00")
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.strict_der])
+        .trusted_certs(root)
+        .peer_certificate(indefinite)
+        .fails()
+    )
+
+
+@testcase
+def trailing_data_ee(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is a valid DER-encoded certificate, followed by trailing zero
+    bytes within the same PEM block. A strict DER parser should reject the
+    encoding as a whole, rather than ignoring the data after the end of
+    the `Certificate` SEQUENCE.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    trailing = RawCertificate(leaf.cert.public_bytes(serialization.Encoding.DER) + b"\x00" * 4)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(trailing)
+        .fails()
+    )
+
+
+@testcase
+def trailing_data_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA is a valid DER-encoded certificate, followed by trailing bytes
+    within the same PEM block. A strict DER parser should reject the ICA,
+    leaving no valid path from the EE to the root.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+
+    trailing = RawCertificate(ica.cert.public_bytes(serialization.Encoding.DER) + b"\xde\xad")
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(trailing)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def trailing_data_root(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The root is a valid DER-encoded certificate, followed by trailing bytes
+    within the same PEM block. A strict DER parser should reject the root,
+    leaving the EE without a trust anchor.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    trailing = RawCertificate(root.cert.public_bytes(serialization.Encoding.DER) + b"\xde\xad")
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(trailing)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def rsa_modulus_leading_zeros(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's RSA-2048 public key encodes its modulus with redundant leading
+    zero bytes, beyond the single zero byte needed to keep the INTEGER
+    positive. DER (X.690 8.3.2) requires INTEGERs to be minimally encoded,
+    so the key is mis-encoded.
+
+    The modulus's numeric value is unchanged, so implementations that
+    tolerate non-minimal INTEGERs will accept this chain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    numbers = key.public_key().public_numbers()
+    # RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+    rsa_public_key = der_tlv(
+        0x30,
+        der_tlv(0x02, b"\x00\x00\x00" + numbers.n.to_bytes(256, "big"))
+        + der_tlv(0x02, numbers.e.to_bytes(3, "big")),
+    )
+    # SubjectPublicKeyInfo ::= SEQUENCE { rsaEncryption, BIT STRING }
+    spki = der_tlv(
+        0x30,
+        bytes.fromhex("300d06092a864886f70d0101010500")
+        + der_tlv(0x03, b"\x00" + rsa_public_key),
+    )
+
+    tampered = tamper(leaf, root.key, tbs=replace_tbs_field(TBS_SPKI_INDEX, spki))
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.strict_asn1_integers])
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .fails()
+    )
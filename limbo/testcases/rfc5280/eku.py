@@ -5,7 +5,7 @@ RFC5280 Extended Key Usage (EKU) tests.
 from cryptography import x509
 
 from limbo.assets import ext
-from limbo.models import KnownEKUs, PeerName
+from limbo.models import CertProfile, Importance, KnownEKUs, PeerName
 from limbo.testcases._core import Builder, testcase
 
 
@@ -65,3 +65,122 @@ def ee_without_eku(builder: Builder) -> None:
         .peer_certificate(leaf)
         .succeeds()
     )
+
+
+@testcase
+def ica_without_eku(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA has no extKeyUsage extension. Validators that treat a CA's EKUs
+    as constraining its subordinates treat an absent EKU as unconstrained,
+    and RFC 5280 4.2.1.12 doesn't require CAs to have the extension at all.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.conflicts_with("webpki::eku::ica-without-eku")
+        .server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .extended_key_usage([KnownEKUs.server_auth])
+        .succeeds()
+    )
+
+
+@testcase
+def required_anyeku_ee_server_auth(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `anyExtendedKeyUsage`, i.e. accepts an EE with any
+    EKU, and the EE's EKU contains `id-kp-serverAuth`.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    builder = (
+        builder.client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(None)
+        .extended_key_usage([KnownEKUs.any])
+        .succeeds()
+    )
+
+
+@testcase
+def required_anyeku_ee_without_eku(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `anyExtendedKeyUsage`, and the EE has no
+    extKeyUsage extension. RFC 5280 4.2.1.12 doesn't require the extension,
+    and an EE without it isn't restricted to any particular purpose.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root, eku=None)
+
+    builder = (
+        builder.conflicts_with("rfc5280::eku::required-anyeku-ee-without-eku-strict")
+        .client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(None)
+        .extended_key_usage([KnownEKUs.any])
+        .succeeds()
+    )
+
+
+@testcase
+def required_anyeku_ee_without_eku_strict(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    This is the same as `required-anyeku-ee-without-eku`, but the validator
+    requires the EE to have an extKeyUsage extension, as RFC 5280 4.2.1.12
+    permits:
+
+    > Certificate using applications MAY require that the extended key usage
+    > extension be present and that a particular purpose be indicated in
+    > order for the certificate to be acceptable to that application.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root, eku=None)
+
+    builder = (
+        builder.conflicts_with("rfc5280::eku::required-anyeku-ee-without-eku")
+        .importance(Importance.LOW)
+        .client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(None)
+        .extended_key_usage([KnownEKUs.any])
+        .fails()
+    )
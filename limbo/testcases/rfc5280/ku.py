@@ -0,0 +1,666 @@
+"""
+RFC5280 Key Usage (KU) tests.
+
+These testcases exercise the `key_usage` testcase field, which lists the
+key usages that the validator requires of the EE. For each usage, there are
+three testcases: one where the EE's KU asserts it, one where the EE's KU
+asserts a broad set of other usages but not it, and one where the EE's KU
+asserts only a similar but different usage instead.
+"""
+
+from cryptography import x509
+
+from limbo.assets import ext
+from limbo.models import KeyUsage, PeerName
+from limbo.testcases._core import Builder, testcase
+
+# The usages that an EE's KU can assert by themselves: `keyCertSign` also
+# requires the `cA` bit (RFC 5280 4.2.1.3), and `encipherOnly` and
+# `decipherOnly` only have meaning alongside `keyAgreement`.
+_STANDALONE_USAGES = {
+    KeyUsage.digital_signature,
+    KeyUsage.content_commitment,
+    KeyUsage.key_encipherment,
+    KeyUsage.data_encipherment,
+    KeyUsage.key_agreement,
+    KeyUsage.crl_sign,
+}
+
+
+def _ku_validation(
+    builder: Builder,
+    *,
+    asserted: set[KeyUsage],
+    required: list[KeyUsage],
+    ca: bool = False,
+) -> Builder:
+    """
+    Produces a `root -> EE` chain in which the EE's (critical) KU extension
+    asserts exactly the `asserted` usages, validated with the `required`
+    usages. If `ca` is set, the EE's basic constraints assert the `cA` bit.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        basic_constraints=(
+            ext(x509.BasicConstraints(ca=True, path_length=None), critical=True) if ca else None
+        ),
+        key_usage=ext(
+            x509.KeyUsage(
+                digital_signature=KeyUsage.digital_signature in asserted,
+                content_commitment=KeyUsage.content_commitment in asserted,
+                key_encipherment=KeyUsage.key_encipherment in asserted,
+                data_encipherment=KeyUsage.data_encipherment in asserted,
+                key_agreement=KeyUsage.key_agreement in asserted,
+                key_cert_sign=KeyUsage.key_cert_sign in asserted,
+                crl_sign=KeyUsage.crl_sign in asserted,
+                encipher_only=KeyUsage.encipher_only in asserted,
+                decipher_only=KeyUsage.decipher_only in asserted,
+            ),
+            critical=True,
+        ),
+    )
+
+    return (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+        .key_usage(required)
+    )
+
+
+@testcase
+def ee_digital_signature_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `digitalSignature`, which the EE's KU asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.digital_signature},
+        required=[KeyUsage.digital_signature],
+    ).succeeds()
+
+
+@testcase
+def ee_digital_signature_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `digitalSignature`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES - {KeyUsage.digital_signature},
+        required=[KeyUsage.digital_signature],
+    ).fails()
+
+
+@testcase
+def ee_digital_signature_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `digitalSignature`, but the EE's KU asserts
+    `contentCommitment` instead.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.content_commitment},
+        required=[KeyUsage.digital_signature],
+    ).fails()
+
+
+@testcase
+def ee_content_commitment_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `contentCommitment`, which the EE's KU asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.content_commitment},
+        required=[KeyUsage.content_commitment],
+    ).succeeds()
+
+
+@testcase
+def ee_content_commitment_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `contentCommitment`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES - {KeyUsage.content_commitment},
+        required=[KeyUsage.content_commitment],
+    ).fails()
+
+
+@testcase
+def ee_content_commitment_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `contentCommitment`, but the EE's KU asserts
+    `digitalSignature` instead.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.digital_signature},
+        required=[KeyUsage.content_commitment],
+    ).fails()
+
+
+@testcase
+def ee_key_encipherment_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyEncipherment`, which the EE's KU asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_encipherment},
+        required=[KeyUsage.key_encipherment],
+    ).succeeds()
+
+
+@testcase
+def ee_key_encipherment_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyEncipherment`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES - {KeyUsage.key_encipherment},
+        required=[KeyUsage.key_encipherment],
+    ).fails()
+
+
+@testcase
+def ee_key_encipherment_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyEncipherment`, but the EE's KU asserts
+    `dataEncipherment` instead.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.data_encipherment},
+        required=[KeyUsage.key_encipherment],
+    ).fails()
+
+
+@testcase
+def ee_data_encipherment_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `dataEncipherment`, which the EE's KU asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.data_encipherment},
+        required=[KeyUsage.data_encipherment],
+    ).succeeds()
+
+
+@testcase
+def ee_data_encipherment_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `dataEncipherment`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES - {KeyUsage.data_encipherment},
+        required=[KeyUsage.data_encipherment],
+    ).fails()
+
+
+@testcase
+def ee_data_encipherment_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `dataEncipherment`, but the EE's KU asserts
+    `keyEncipherment` instead.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_encipherment},
+        required=[KeyUsage.data_encipherment],
+    ).fails()
+
+
+@testcase
+def ee_key_agreement_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyAgreement`, which the EE's KU asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_agreement},
+        required=[KeyUsage.key_agreement],
+    ).succeeds()
+
+
+@testcase
+def ee_key_agreement_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyAgreement`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES - {KeyUsage.key_agreement},
+        required=[KeyUsage.key_agreement],
+    ).fails()
+
+
+@testcase
+def ee_key_agreement_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyAgreement`, but the EE's KU asserts
+    `keyEncipherment` instead.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_encipherment},
+        required=[KeyUsage.key_agreement],
+    ).fails()
+
+
+@testcase
+def ee_key_cert_sign_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyCertSign`, which the EE's KU asserts.
+
+    RFC 5280 4.2.1.3 requires `keyCertSign` to be accompanied by the
+    `cA` bit, so the EE asserts it too.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_cert_sign},
+        required=[KeyUsage.key_cert_sign],
+        ca=True,
+    ).succeeds()
+
+
+@testcase
+def ee_key_cert_sign_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyCertSign`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+
+    The EE asserts the `cA` bit, as for the other `keyCertSign` testcases.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES,
+        required=[KeyUsage.key_cert_sign],
+        ca=True,
+    ).fails()
+
+
+@testcase
+def ee_key_cert_sign_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `keyCertSign`, but the EE's KU asserts
+    `cRLSign` instead.
+
+    RFC 5280 4.2.1.3 requires `keyCertSign` to be accompanied by the
+    `cA` bit, so the EE asserts it too.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.crl_sign},
+        required=[KeyUsage.key_cert_sign],
+        ca=True,
+    ).fails()
+
+
+@testcase
+def ee_crl_sign_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `cRLSign`, which the EE's KU asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.crl_sign},
+        required=[KeyUsage.crl_sign],
+    ).succeeds()
+
+
+@testcase
+def ee_crl_sign_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `cRLSign`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES - {KeyUsage.crl_sign},
+        required=[KeyUsage.crl_sign],
+    ).fails()
+
+
+@testcase
+def ee_crl_sign_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `cRLSign`, but the EE's KU asserts
+    `keyCertSign` instead.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_cert_sign},
+        required=[KeyUsage.crl_sign],
+    ).fails()
+
+
+@testcase
+def ee_encipher_only_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `encipherOnly`, which the EE's KU asserts.
+
+    `encipherOnly` only has meaning alongside `keyAgreement`, which the
+    EE's KU also asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_agreement, KeyUsage.encipher_only},
+        required=[KeyUsage.encipher_only],
+    ).succeeds()
+
+
+@testcase
+def ee_encipher_only_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `encipherOnly`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES,
+        required=[KeyUsage.encipher_only],
+    ).fails()
+
+
+@testcase
+def ee_encipher_only_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `encipherOnly`, but the EE's KU asserts
+    `decipherOnly` instead.
+
+    `encipherOnly` only has meaning alongside `keyAgreement`, which the
+    EE's KU also asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_agreement, KeyUsage.decipher_only},
+        required=[KeyUsage.encipher_only],
+    ).fails()
+
+
+@testcase
+def ee_decipher_only_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `decipherOnly`, which the EE's KU asserts.
+
+    `decipherOnly` only has meaning alongside `keyAgreement`, which the
+    EE's KU also asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_agreement, KeyUsage.decipher_only},
+        required=[KeyUsage.decipher_only],
+    ).succeeds()
+
+
+@testcase
+def ee_decipher_only_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `decipherOnly`, but the EE's KU asserts a broad set
+    of other usages, not including it.
+    """
+
+    _ku_validation(
+        builder,
+        asserted=_STANDALONE_USAGES,
+        required=[KeyUsage.decipher_only],
+    ).fails()
+
+
+@testcase
+def ee_decipher_only_wrong_bit(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires `decipherOnly`, but the EE's KU asserts
+    `encipherOnly` instead.
+
+    `decipherOnly` only has meaning alongside `keyAgreement`, which the
+    EE's KU also asserts.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.key_agreement, KeyUsage.encipher_only},
+        required=[KeyUsage.decipher_only],
+    ).fails()
+
+
+@testcase
+def ee_multiple_required_present(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires both `digitalSignature` and `keyEncipherment`,
+    and the EE's KU asserts both.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.digital_signature, KeyUsage.key_encipherment},
+        required=[KeyUsage.digital_signature, KeyUsage.key_encipherment],
+    ).succeeds()
+
+
+@testcase
+def ee_multiple_required_one_absent(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The validator requires both `digitalSignature` and `keyEncipherment`,
+    but the EE's KU asserts only `digitalSignature`. Every required usage
+    must be asserted, not just one of them.
+    """
+
+    _ku_validation(
+        builder,
+        asserted={KeyUsage.digital_signature},
+        required=[KeyUsage.digital_signature, KeyUsage.key_encipherment],
+    ).fails()
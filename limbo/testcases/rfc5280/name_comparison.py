@@ -0,0 +1,254 @@
+"""
+RFC 5280 distinguished name comparison testcases.
+"""
+
+from cryptography import x509
+from cryptography.x509.oid import NameOID
+
+from limbo.assets import TBS_ISSUER_INDEX, der_tlv, replace_tbs_field, tamper
+from limbo.testcases._core import Builder, testcase
+
+
+def _cn_name(tag: int, value: bytes) -> bytes:
+    """
+    Encodes a `Name` consisting of a single `CN` attribute, whose value is
+    `value` encoded as the string type with the given (single-byte) `tag`.
+    """
+
+    # Name ::= SEQUENCE { SET { SEQUENCE { id-at-commonName, value } } }
+    return der_tlv(
+        0x30,
+        der_tlv(0x31, der_tlv(0x30, bytes.fromhex("0603550403") + der_tlv(tag, value))),
+    )
+
+
+@testcase
+def multi_value_rdn(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA's subject consists of a single multi-valued RDN containing
+    both `CN` and `O` attributes. The EE's issuer contains the same
+    multi-valued RDN, but encoded with its attributes in the opposite
+    (non-canonical) order.
+
+    An RDN is a `SET OF` attributes, so attribute order within it is not
+    significant, and implementations that compare names byte-for-byte will
+    incorrectly fail to build this chain. However, the EE's issuer is not
+    valid DER (X.690 11.6), so implementations that require DER will also
+    reject it; see `rfc5280::der::ber-set-order`.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        subject=x509.Name(
+            [
+                x509.RelativeDistinguishedName(
+                    [
+                        x509.NameAttribute(NameOID.COMMON_NAME, "x509-limbo-intermediate"),
+                        x509.NameAttribute(NameOID.ORGANIZATION_NAME, "x509-limbo"),
+                    ]
+                )
+            ]
+        ),
+    )
+    leaf = builder.leaf_cert(ica)
+
+    # Under DER, the shorter O attribute (`30 11 ...`) sorts before the CN
+    # attribute (`30 1e ...`); we deliberately encode them in the opposite order.
+    attributes = [
+        der_tlv(0x30, bytes.fromhex("0603550403") + der_tlv(0x0C, b"x509-limbo-intermediate")),
+        der_tlv(0x30, bytes.fromhex("060355040a") + der_tlv(0x0C, b"x509-limbo")),
+    ]
+    assert attributes != sorted(attributes)
+    issuer = der_tlv(0x30, der_tlv(0x31, b"".join(attributes)))
+    tampered = tamper(leaf, ica.key, tbs=replace_tbs_field(TBS_ISSUER_INDEX, issuer))
+
+    builder = (
+        builder.server_validation()
+        .conflicts_with("rfc5280::der::ber-set-order")
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(tampered)
+        .succeeds()
+    )
+
+
+@testcase
+def multi_value_rdn_split(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA's subject consists of a single multi-valued RDN containing
+    both `CN` and `O` attributes. The EE's issuer contains the same
+    attributes, but split across two single-valued RDNs.
+
+    These are different names: a name is a `SEQUENCE OF` RDNs, and
+    `{CN, O}` is not the same RDN sequence as `{O}, {CN}`. As a result,
+    the ICA cannot be the EE's issuer and no chain can be built.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        subject=x509.Name(
+            [
+                x509.RelativeDistinguishedName(
+                    [
+                        x509.NameAttribute(NameOID.COMMON_NAME, "x509-limbo-intermediate"),
+                        x509.NameAttribute(NameOID.ORGANIZATION_NAME, "x509-limbo"),
+                    ]
+                )
+            ]
+        ),
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        issuer=x509.Name(
+            [
+                x509.NameAttribute(NameOID.ORGANIZATION_NAME, "x509-limbo"),
+                x509.NameAttribute(NameOID.COMMON_NAME, "x509-limbo-intermediate"),
+            ]
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def printablestring_utf8string_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA's subject `CN` is encoded as a `UTF8String`, while the EE's
+    issuer `CN` has the same value encoded as a `PrintableString`.
+
+    RFC 5280 7.1 requires that these be compared after normalization,
+    rather than byte-for-byte:
+
+    > Conforming implementations MUST use the LDAP StringPrep profile
+    > (including insignificant space handling), as specified in [RFC4518],
+    > as the basis for comparison of distinguished name attributes encoded
+    > in either PrintableString or UTF8String.
+
+    Implementations that compare names byte-for-byte will incorrectly fail
+    to build this chain.
+    """
+
+    root = builder.root_ca()
+    # cryptography encodes CN as a UTF8String by default.
+    ica = builder.intermediate_ca(
+        root,
+        subject=x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "x509-limbo-intermediate")]),
+    )
+    leaf = builder.leaf_cert(ica)
+    # PrintableString
+    issuer = _cn_name(0x13, b"x509-limbo-intermediate")
+    tampered = tamper(leaf, ica.key, tbs=replace_tbs_field(TBS_ISSUER_INDEX, issuer))
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(tampered)
+        .succeeds()
+    )
+
+
+@testcase
+def bmpstring_utf8string_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA's subject `CN` is encoded as a `UTF8String`, while the EE's
+    issuer `CN` has the same value encoded as a (legacy) `BMPString`.
+
+    RFC 5280 7.1 compares attribute values after RFC 4518 string preparation,
+    the first step of which transcodes every `DirectoryString` choice to
+    Unicode. After preparation the two values are identical, so the EE's
+    issuer matches the ICA's subject.
+    """
+
+    root = builder.root_ca()
+    # cryptography encodes CN as a UTF8String by default.
+    ica = builder.intermediate_ca(
+        root,
+        subject=x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "x509-limbo-intermediate")]),
+    )
+    leaf = builder.leaf_cert(ica)
+    # BMPString (UCS-2, i.e. big-endian UTF-16 within the BMP)
+    issuer = _cn_name(0x1E, "x509-limbo-intermediate".encode("utf-16-be"))
+    tampered = tamper(leaf, ica.key, tbs=replace_tbs_field(TBS_ISSUER_INDEX, issuer))
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(tampered)
+        .succeeds()
+    )
+
+
+@testcase
+def teletexstring_non_utf8(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA's subject `CN` is `x509-limbo-intermédiaire`, encoded as a
+    `UTF8String`. The EE's issuer `CN` is a `TeletexString` containing
+    the same text with a raw `0xE9` byte in place of `é`, which is neither
+    valid UTF-8 nor `é` under T.61 (which spells it as a combining acute
+    accent followed by `e`).
+
+    The EE's issuer therefore does not match the ICA's subject, and
+    implementations that cannot decode `TeletexString` should fail to
+    build this chain rather than falling back to a lossy interpretation.
+    """
+
+    root = builder.root_ca()
+    # cryptography encodes CN as a UTF8String by default.
+    ica = builder.intermediate_ca(
+        root,
+        subject=x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, "x509-limbo-intermédiaire")]),
+    )
+    leaf = builder.leaf_cert(ica)
+
+    # TeletexString
+    issuer = _cn_name(0x14, b"x509-limbo-interm\xe9diaire")
+
+    tampered = tamper(leaf, ica.key, tbs=replace_tbs_field(TBS_ISSUER_INDEX, issuer))
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(tampered)
+        .fails()
+    )
@@ -7,8 +7,8 @@ from ipaddress import IPv4Address, IPv4Network, IPv6Address, IPv6Network
 from cryptography import x509
 from cryptography.hazmat.primitives.asymmetric import ec
 
-from limbo.assets import ext
-from limbo.models import Feature, KnownEKUs, PeerName
+from limbo.assets import der_tlv, ext
+from limbo.models import Feature, Importance, KnownEKUs, PeerName
 from limbo.testcases._core import Builder, testcase
 
 
@@ -577,6 +577,163 @@ def excluded_dn_match_sub_mismatch(builder: Builder) -> None:
     builder.trusted_certs(root).peer_certificate(leaf).fails()
 
 
+_EXAMPLE_ORG_DN = x509.Name(
+    [
+        x509.NameAttribute(x509.NameOID.COUNTRY_NAME, "US"),
+        x509.NameAttribute(x509.NameOID.ORGANIZATION_NAME, "Example"),
+    ]
+)
+
+
+@testcase
+def permitted_dn_subtree_match(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension with a permitted DirectoryName
+    of `C=US, O=Example`. The leaf's subject is `C=US, O=Example, CN=example.com`,
+    which is within the permitted subtree since the constraint's RDNs are
+    a prefix of the subject's RDNs, per RFC 5280 4.2.1.10.
+    """
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.DirectoryName(_EXAMPLE_ORG_DN)],
+                excluded_subtrees=None,
+            ),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        subject=x509.Name(
+            [*_EXAMPLE_ORG_DN, x509.NameAttribute(x509.NameOID.COMMON_NAME, "example.com")]
+        ),
+    )
+
+    builder = builder.server_validation().features([Feature.name_constraint_dn])
+    builder.trusted_certs(root).peer_certificate(leaf).succeeds()
+
+
+@testcase
+def permitted_dn_subtree_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension with a permitted DirectoryName
+    of `C=US, O=Example`. The leaf's subject is `C=US, O=Other, CN=example.com`,
+    which is not within the permitted subtree.
+    """
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.DirectoryName(_EXAMPLE_ORG_DN)],
+                excluded_subtrees=None,
+            ),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        subject=x509.Name(
+            [
+                x509.NameAttribute(x509.NameOID.COUNTRY_NAME, "US"),
+                x509.NameAttribute(x509.NameOID.ORGANIZATION_NAME, "Other"),
+                x509.NameAttribute(x509.NameOID.COMMON_NAME, "example.com"),
+            ]
+        ),
+    )
+
+    builder = builder.server_validation().features([Feature.name_constraint_dn])
+    builder.trusted_certs(root).peer_certificate(leaf).fails()
+
+
+@testcase
+def permitted_dn_subtree_not_prefix(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension with a permitted DirectoryName
+    of `C=US, O=Example`. The leaf's subject is `C=US, CN=example.com, O=Example`,
+    which contains the same attributes as the constraint but not as a prefix of
+    its RDN sequence, and is therefore not within the permitted subtree.
+    """
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.DirectoryName(_EXAMPLE_ORG_DN)],
+                excluded_subtrees=None,
+            ),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        subject=x509.Name(
+            [
+                x509.NameAttribute(x509.NameOID.COUNTRY_NAME, "US"),
+                x509.NameAttribute(x509.NameOID.COMMON_NAME, "example.com"),
+                x509.NameAttribute(x509.NameOID.ORGANIZATION_NAME, "Example"),
+            ]
+        ),
+    )
+
+    builder = builder.server_validation().features([Feature.name_constraint_dn])
+    builder.trusted_certs(root).peer_certificate(leaf).fails()
+
+
+@testcase
+def excluded_dn_subtree_match(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> leaf
+    ```
+
+    The ICA contains a NameConstraints extension with an excluded DirectoryName
+    of `C=US, O=Example`. The leaf's subject is
+    `C=US, O=Example, OU=Engineering, CN=example.com`, which is within the
+    excluded subtree.
+    """
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=None,
+                excluded_subtrees=[x509.DirectoryName(_EXAMPLE_ORG_DN)],
+            ),
+            critical=True,
+        ),
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        subject=x509.Name(
+            [
+                *_EXAMPLE_ORG_DN,
+                x509.NameAttribute(x509.NameOID.ORGANIZATIONAL_UNIT_NAME, "Engineering"),
+                x509.NameAttribute(x509.NameOID.COMMON_NAME, "example.com"),
+            ]
+        ),
+    )
+
+    builder = builder.server_validation().features([Feature.name_constraint_dn])
+    builder.trusted_certs(root).untrusted_intermediates(ica).peer_certificate(leaf).fails()
+
+
 # NOTE: The following tests aren't specific to any name constraint type.
 # We could potentially parametrize this for different constraint types.
 @testcase
@@ -708,69 +865,86 @@ def excluded_match_permitted_and_excluded(builder: Builder) -> None:
 
 
 @testcase
-def permitted_different_constraint_type(builder: Builder) -> None:
+def excluded_dns_narrows_permitted(builder: Builder) -> None:
     """
-    Produces the following **valid** chain:
+    Produces the following **invalid** chain:
 
     ```
     root -> leaf
     ```
 
-    The root contains a NameConstraints extension with a permitted iPAddress of
-    `192.0.2.0/24`, while the leaf's SubjectAlternativeName is a dNSName.
+    The root contains a NameConstraints extension with a permitted dNSName of
+    "example.com" and an excluded dNSName of "bad.example.com". The leaf's
+    SubjectAlternativeName of "bad.example.com" satisfies the permitted subtree,
+    but also falls within the excluded subtree.
+
+    Per RFC 5280 6.1.3 (b), names must be within the permitted subtrees
+    **and** not within the excluded subtrees, so the exclusion takes precedence.
     """
     root = builder.root_ca(
         name_constraints=ext(
             x509.NameConstraints(
-                permitted_subtrees=[x509.IPAddress(IPv4Network("192.0.2.0/24"))],
-                excluded_subtrees=None,
+                permitted_subtrees=[x509.DNSName("example.com")],
+                excluded_subtrees=[x509.DNSName("bad.example.com")],
             ),
             critical=True,
         )
     )
     leaf = builder.leaf_cert(
         root,
-        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("bad.example.com")]), critical=False),
     )
     builder = builder.server_validation()
     builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
-        PeerName(kind="DNS", value="example.com")
-    ).succeeds()
+        PeerName(kind="DNS", value="bad.example.com")
+    ).fails()
 
 
 @testcase
-def excluded_different_constraint_type(builder: Builder) -> None:
+def excluded_email_narrows_permitted(builder: Builder) -> None:
     """
-    Produces the following **valid** chain:
+    Produces the following **invalid** chain:
 
     ```
     root -> leaf
     ```
 
-    The root contains a NameConstraints extension with an excluded iPAddress of
-    `192.0.2.0/24`, while the leaf's SubjectAlternativeName is a dNSName.
+    The root contains a NameConstraints extension with a permitted rfc822Name of
+    "example.com" and an excluded rfc822Name of "bad@example.com". The leaf's
+    SubjectAlternativeName of "bad@example.com" satisfies the permitted subtree,
+    but also falls within the excluded subtree, which takes precedence per
+    RFC 5280 6.1.3 (b).
     """
     root = builder.root_ca(
         name_constraints=ext(
             x509.NameConstraints(
-                permitted_subtrees=None,
-                excluded_subtrees=[x509.IPAddress(IPv4Network("192.0.2.0/24"))],
+                permitted_subtrees=[x509.RFC822Name("example.com")],
+                excluded_subtrees=[x509.RFC822Name("bad@example.com")],
             ),
             critical=True,
         )
     )
     leaf = builder.leaf_cert(
         root,
-        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+        san=ext(x509.SubjectAlternativeName([x509.RFC822Name("bad@example.com")]), critical=False),
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_names(PeerName(kind="RFC822", value="bad@example.com"))
+        .fails()
     )
-    builder = builder.server_validation()
-    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
-        PeerName(kind="DNS", value="example.com")
-    ).succeeds()
 
 
 @testcase
-def invalid_dnsname_wildcard(builder: Builder) -> None:
+def excluded_ip_narrows_permitted(builder: Builder) -> None:
     """
     Produces the following **invalid** chain:
 
@@ -778,30 +952,36 @@ def invalid_dnsname_wildcard(builder: Builder) -> None:
     root -> leaf
     ```
 
-    The root contains a Name Constraints extension with a malformed dNSName
-    (uses a wildcard pattern, which is not permitted under RFC 5280).
+    The root contains a NameConstraints extension with a permitted iPAddress of
+    `192.0.2.0/24` and an excluded iPAddress of `192.0.2.0/28`. The leaf's
+    SubjectAlternativeName of `192.0.2.1` satisfies the permitted subtree,
+    but also falls within the excluded subtree, which takes precedence per
+    RFC 5280 6.1.3 (b).
     """
-
-    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
-    name_constraints = x509.NameConstraints(
-        permitted_subtrees=[x509.DNSName("unrelated.cryptography.io")], excluded_subtrees=None
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.IPAddress(IPv4Network("192.0.2.0/24"))],
+                excluded_subtrees=[x509.IPAddress(IPv4Network("192.0.2.0/28"))],
+            ),
+            critical=True,
+        )
     )
-    name_constraints._permitted_subtrees = [x509.DNSName("*.example.com")]
-
-    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
     leaf = builder.leaf_cert(
         root,
-        san=ext(x509.SubjectAlternativeName([x509.DNSName("foo.example.com")]), critical=False),
+        san=ext(
+            x509.SubjectAlternativeName([x509.IPAddress(IPv4Address("192.0.2.1"))]), critical=False
+        ),
     )
 
     builder = builder.server_validation()
     builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
-        PeerName(kind="DNS", value="foo.example.com")
+        PeerName(kind="IP", value="192.0.2.1")
     ).fails()
 
 
 @testcase
-def invalid_dnsname_leading_period(builder: Builder) -> None:
+def excluded_dns_match_outside_permitted(builder: Builder) -> None:
     """
     Produces the following **invalid** chain:
 
@@ -809,34 +989,32 @@ def invalid_dnsname_leading_period(builder: Builder) -> None:
     root -> leaf
     ```
 
-    The root contains a Name Constraint extension with a malformed DNS name
-    (uses a leading period, which is not permitted under RFC 5280 4.2.1.10).
-
-    This is widely (incorrectly) accepted by implementations due to OpenSSL
-    accepting it and due to misreadings of RFC 5280, which allows a leading
-    period in *URI* constraints but not DNS constraints.
+    The root contains a NameConstraints extension with a permitted dNSName of
+    "example.com" and an excluded dNSName of "example.net". The leaf's
+    SubjectAlternativeName of "example.net" falls within the excluded subtree
+    and outside of the permitted subtree, so it is rejected by both.
     """
-
-    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
-    name_constraints = x509.NameConstraints(
-        permitted_subtrees=[x509.DNSName("unrelated.cryptography.io")], excluded_subtrees=None
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.DNSName("example.com")],
+                excluded_subtrees=[x509.DNSName("example.net")],
+            ),
+            critical=True,
+        )
     )
-    name_constraints._permitted_subtrees = [x509.DNSName(".example.com")]
-
-    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
     leaf = builder.leaf_cert(
         root,
-        san=ext(x509.SubjectAlternativeName([x509.DNSName("foo.example.com")]), critical=False),
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.net")]), critical=False),
     )
-
     builder = builder.server_validation()
     builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
-        PeerName(kind="DNS", value="foo.example.com")
+        PeerName(kind="DNS", value="example.net")
     ).fails()
 
 
 @testcase
-def invalid_ipv4_address(builder: Builder) -> None:
+def permitted_and_excluded_dns_neither_match(builder: Builder) -> None:
     """
     Produces the following **invalid** chain:
 
@@ -844,102 +1022,455 @@ def invalid_ipv4_address(builder: Builder) -> None:
     root -> leaf
     ```
 
-    The root contains a NameConstraints extension with a malformed IPv4
-    iPAddress (not in CIDR form).
-    """
+    The root contains a NameConstraints extension with a permitted dNSName of
+    "example.com" and an excluded dNSName of "bad.example.com". The leaf's
+    SubjectAlternativeName of "example.org" is in neither subtree.
 
-    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
-    name_constraints = x509.NameConstraints(
-        permitted_subtrees=[x509.IPAddress(IPv4Network("0.0.0.0/8"))], excluded_subtrees=None
+    Because the permitted subtrees contain a dNSName, every dNSName in
+    the leaf must fall within them, so the leaf is rejected.
+    """
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.DNSName("example.com")],
+                excluded_subtrees=[x509.DNSName("bad.example.com")],
+            ),
+            critical=True,
+        )
     )
-    name_constraints._permitted_subtrees = [x509.IPAddress(IPv4Address("127.0.0.1"))]
-
-    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
     leaf = builder.leaf_cert(
         root,
-        san=ext(
-            x509.SubjectAlternativeName([x509.IPAddress(IPv4Address("127.0.0.1"))]), critical=False
-        ),
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.org")]), critical=False),
     )
-
     builder = builder.server_validation()
     builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
-        PeerName(kind="IP", value="127.0.0.1")
+        PeerName(kind="DNS", value="example.org")
     ).fails()
 
 
 @testcase
-def invalid_ipv6_address(builder: Builder) -> None:
+def excluded_dns_only_neither_match(builder: Builder) -> None:
     """
-    Produces the following **invalid** chain:
+    Produces the following **valid** chain:
 
     ```
     root -> leaf
     ```
 
-    The root contains a NameConstraints extension with a malformed IPv6
-    iPAddress (not in CIDR form).
+    The root contains a NameConstraints extension with only an excluded
+    dNSName of "bad.example.com". The leaf's SubjectAlternativeName of
+    "example.com" is not within the excluded subtree, and there are no
+    permitted subtrees to satisfy, so the leaf is accepted.
     """
-
-    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
-    name_constraints = x509.NameConstraints(
-        permitted_subtrees=[x509.IPAddress(IPv6Network("::1/128"))], excluded_subtrees=None
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=None,
+                excluded_subtrees=[x509.DNSName("bad.example.com")],
+            ),
+            critical=True,
+        )
     )
-    name_constraints._permitted_subtrees = [x509.IPAddress(IPv6Address("::1"))]
-
-    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
     leaf = builder.leaf_cert(
         root,
-        san=ext(x509.SubjectAlternativeName([x509.IPAddress(IPv6Address("::1"))]), critical=False),
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
     )
-
     builder = builder.server_validation()
     builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
-        PeerName(kind="IP", value="::1")
-    ).fails()
+        PeerName(kind="DNS", value="example.com")
+    ).succeeds()
 
 
 @testcase
-def invalid_email_address(builder: Builder) -> None:
+def permitted_different_constraint_type(builder: Builder) -> None:
     """
-    Produces the following **invalid** chain:
+    Produces the following **valid** chain:
 
     ```
     root -> leaf
     ```
 
-    The root contains a `NameConstraints` extension with a malformed
-    RFC822 name constraint (`invalid@invalid@example.com`, which is not
-    a valid email address).
+    The root contains a NameConstraints extension with a permitted iPAddress of
+    `192.0.2.0/24`, while the leaf's SubjectAlternativeName is a dNSName.
+    """
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.IPAddress(IPv4Network("192.0.2.0/24"))],
+                excluded_subtrees=None,
+            ),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+    )
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).succeeds()
+
+
+@testcase
+def excluded_different_constraint_type(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension with an excluded iPAddress of
+    `192.0.2.0/24`, while the leaf's SubjectAlternativeName is a dNSName.
+    """
+    root = builder.root_ca(
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=None,
+                excluded_subtrees=[x509.IPAddress(IPv4Network("192.0.2.0/24"))],
+            ),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+    )
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).succeeds()
+
+
+@testcase
+def invalid_dnsname_wildcard(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a Name Constraints extension with a malformed dNSName
+    (uses a wildcard pattern, which is not permitted under RFC 5280).
+    """
+
+    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
+    name_constraints = x509.NameConstraints(
+        permitted_subtrees=[x509.DNSName("unrelated.cryptography.io")], excluded_subtrees=None
+    )
+    name_constraints._permitted_subtrees = [x509.DNSName("*.example.com")]
+
+    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("foo.example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="foo.example.com")
+    ).fails()
+
+
+@testcase
+def invalid_dnsname_leading_period(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a Name Constraint extension with a malformed DNS name
+    (uses a leading period, which is not permitted under RFC 5280 4.2.1.10).
+
+    This is widely (incorrectly) accepted by implementations due to OpenSSL
+    accepting it and due to misreadings of RFC 5280, which allows a leading
+    period in *URI* constraints but not DNS constraints.
+    """
+
+    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
+    name_constraints = x509.NameConstraints(
+        permitted_subtrees=[x509.DNSName("unrelated.cryptography.io")], excluded_subtrees=None
+    )
+    name_constraints._permitted_subtrees = [x509.DNSName(".example.com")]
+
+    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("foo.example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="foo.example.com")
+    ).fails()
+
+
+@testcase
+def invalid_ipv4_address(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension with a malformed IPv4
+    iPAddress (not in CIDR form).
+    """
+
+    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
+    name_constraints = x509.NameConstraints(
+        permitted_subtrees=[x509.IPAddress(IPv4Network("0.0.0.0/8"))], excluded_subtrees=None
+    )
+    name_constraints._permitted_subtrees = [x509.IPAddress(IPv4Address("127.0.0.1"))]
+
+    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName([x509.IPAddress(IPv4Address("127.0.0.1"))]), critical=False
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="IP", value="127.0.0.1")
+    ).fails()
+
+
+@testcase
+def invalid_ipv6_address(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension with a malformed IPv6
+    iPAddress (not in CIDR form).
+    """
+
+    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
+    name_constraints = x509.NameConstraints(
+        permitted_subtrees=[x509.IPAddress(IPv6Network("::1/128"))], excluded_subtrees=None
+    )
+    name_constraints._permitted_subtrees = [x509.IPAddress(IPv6Address("::1"))]
+
+    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.IPAddress(IPv6Address("::1"))]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="IP", value="::1")
+    ).fails()
+
+
+@testcase
+def invalid_email_address(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a `NameConstraints` extension with a malformed
+    RFC822 name constraint (`invalid@invalid@example.com`, which is not
+    a valid email address).
+    """
+    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
+    name_constraints = x509.NameConstraints(
+        permitted_subtrees=[x509.RFC822Name("fake@example.com")], excluded_subtrees=None
+    )
+    name_constraints._permitted_subtrees = [
+        x509.RFC822Name._init_without_validation("invalid@invalid@example.com")
+    ]
+
+    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName([x509.RFC822Name("example@example.com")]), critical=False
+        ),
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_names(PeerName(kind="RFC822", value="example@example.com"))
+        .fails()
+    )
+
+
+@testcase
+def permitted_subtrees_empty(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension whose `permittedSubtrees`
+    is present but empty. This is forbidden by the ASN.1 definition in
+    RFC 5280 4.2.1.10:
+
+    > GeneralSubtrees ::= SEQUENCE SIZE (1..MAX) OF GeneralSubtree
+
+    Even an implementation that tolerates the malformed encoding must reject
+    the leaf: per RFC 5280 6.1.3 (b), every name in the leaf must fall within
+    the permitted subtrees, and an empty set of subtrees permits no names.
+    """
+    # NOTE: cryptography refuses to build an empty `permittedSubtrees`, so we
+    # encode the extension by hand: SEQUENCE { [0] {} }
+    root = builder.root_ca(
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.OID_NAME_CONSTRAINTS, b"\x30\x02\xa0\x00"),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).fails()
+
+
+@testcase
+def permitted_subtrees_empty_no_san(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    This is the same as `rfc5280::nc::permitted-subtrees-empty`, except that
+    the leaf has no SubjectAlternativeName at all and is validated as a client
+    certificate.
+
+    The leaf must still be rejected: the root's NameConstraints extension is
+    malformed per RFC 5280 4.2.1.10, since `permittedSubtrees` is present but
+    empty.
+    """
+    root = builder.root_ca(
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.OID_NAME_CONSTRAINTS, b"\x30\x02\xa0\x00"),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        san=None,
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def name_constraints_empty(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    The root contains a NameConstraints extension that is an empty SEQUENCE,
+    i.e. both `permittedSubtrees` and `excludedSubtrees` are absent. This is
+    forbidden by RFC 5280 4.2.1.10:
+
+    > Conforming CAs MUST NOT issue certificates where name constraints is an
+    > empty sequence.  That is, either the permittedSubtrees field or the
+    > excludedSubtrees MUST be present.
+
+    A lenient implementation may instead treat this extension as a no-op
+    that permits all names, which is why this testcase is marked as pedantic.
+
+    This is the strict variant of `name-constraints-empty-permits-all`.
+    """
+    root = builder.root_ca(
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.OID_NAME_CONSTRAINTS, b"\x30\x00"),
+            critical=True,
+        )
+    )
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.pedantic_rfc5280])
+        .conflicts_with("rfc5280::nc::name-constraints-empty-permits-all")
+    )
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).fails()
+
+
+@testcase
+def name_constraints_empty_permits_all(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> leaf
+    ```
+
+    This is the same as `name-constraints-empty`, but the NameConstraints
+    extension is treated as a no-op. With neither `permittedSubtrees` nor
+    `excludedSubtrees` present, the extension constrains nothing, so every
+    name is permitted.
     """
-    # NOTE: Set `_permitted_subtrees` directly to avoid validation.
-    name_constraints = x509.NameConstraints(
-        permitted_subtrees=[x509.RFC822Name("fake@example.com")], excluded_subtrees=None
+    root = builder.root_ca(
+        extra_extension=ext(
+            x509.UnrecognizedExtension(x509.OID_NAME_CONSTRAINTS, b"\x30\x00"),
+            critical=True,
+        )
     )
-    name_constraints._permitted_subtrees = [
-        x509.RFC822Name._init_without_validation("invalid@invalid@example.com")
-    ]
-
-    root = builder.root_ca(name_constraints=ext(name_constraints, critical=True))
     leaf = builder.leaf_cert(
         root,
-        san=ext(
-            x509.SubjectAlternativeName([x509.RFC822Name("example@example.com")]), critical=False
-        ),
-        eku=ext(
-            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
-            critical=False,
-        ),
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
     )
 
     builder = (
-        builder.client_validation()
-        .extended_key_usage([KnownEKUs.client_auth])
-        .trusted_certs(root)
-        .peer_certificate(leaf)
-        .expected_peer_names(PeerName(kind="RFC822", value="example@example.com"))
-        .fails()
+        builder.server_validation()
+        .importance(Importance.LOW)
+        .conflicts_with("rfc5280::nc::name-constraints-empty")
     )
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).succeeds()
 
 
 @testcase
@@ -1481,61 +2012,293 @@ def nc_forbids_same_chain_ica(builder: Builder) -> None:
         root_a,
         name_constraints=ext(
             x509.NameConstraints(
-                permitted_subtrees=None, excluded_subtrees=[x509.DNSName("forbidden.example.com")]
+                permitted_subtrees=None, excluded_subtrees=[x509.DNSName("forbidden.example.com")]
+            ),
+            critical=True,
+        ),
+        san=None,
+    )
+
+    ica_b_key = ec.generate_private_key(ec.SECP256R1())
+    ica_b_1 = builder.intermediate_ca(
+        ica_a,
+        key=ica_b_key,
+        subject=x509.Name.from_rfc4514_string("CN=an-intermediate"),
+        san=ext(
+            x509.SubjectAlternativeName([x509.DNSName("forbidden.example.com")]), critical=False
+        ),
+    )
+    ica_b_2 = builder.intermediate_ca(
+        root_b,
+        key=ica_b_key,
+        subject=x509.Name.from_rfc4514_string("CN=an-intermediate"),
+        san=ext(
+            x509.SubjectAlternativeName([x509.DNSName("unconstrained-ica.example.com")]),
+            critical=False,
+        ),
+    )
+
+    leaf = builder.leaf_cert(
+        ica_b_1,
+        subject=x509.Name.from_rfc4514_string("CN=unconstrained.example.com"),
+        san=ext(
+            x509.SubjectAlternativeName([x509.DNSName("unconstrained.example.com")]), critical=False
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root_a, root_b)
+        .untrusted_intermediates(ica_a, ica_b_1, ica_b_2)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="unconstrained.example.com"))
+        .succeeds()
+    )
+
+
+@testcase
+def nc_permits_email_exact(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root -> ICA (permit: foo@example.com) -> EE (SAN: foo@example.com)
+    ```
+
+    Per RFC 5280 4.2.1.10 an email name constraint may specify a particular mailbox,
+    like in this graph.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.RFC822Name("foo@example.com")], excluded_subtrees=None
+            ),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        san=ext(x509.SubjectAlternativeName([x509.RFC822Name("foo@example.com")]), critical=False),
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_names(PeerName(kind="RFC822", value="foo@example.com"))
+        .succeeds()
+    )
+
+
+@testcase
+def nc_permits_email_domain(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root -> ICA (permit: example.com) -> EE (SAN: foo@example.com)
+    ```
+
+    Per RFC 5280 4.2.1.10 an email name constraint may specify a host name to
+    constrain all inboxes on that host.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.RFC822Name("example.com")], excluded_subtrees=None
+            ),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        san=ext(x509.SubjectAlternativeName([x509.RFC822Name("foo@example.com")]), critical=False),
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_names(PeerName(kind="RFC822", value="foo@example.com"))
+        .succeeds()
+    )
+
+
+@testcase
+def nc_permits_email_subdomain(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root -> ICA (permit: .example.com) -> EE (SAN: foo@sub.example.com)
+    ```
+
+    Per RFC 5280 4.2.1.10 an email name constraint may specify a domain
+    with a leading period, constraining all inboxes on hosts within that domain:
+
+    > To indicate all Internet mail addresses on hosts in a
+    > particular domain, the constraint is specified as the domain name with a
+    > leading period (e.g., ".example.com").
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.RFC822Name(".example.com")], excluded_subtrees=None
+            ),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        san=ext(
+            x509.SubjectAlternativeName([x509.RFC822Name("foo@sub.example.com")]), critical=False
+        ),
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_names(PeerName(kind="RFC822", value="foo@sub.example.com"))
+        .succeeds()
+    )
+
+
+@testcase
+def nc_forbids_email_mailbox_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **invalid** graph:
+
+    ```
+    root -> ICA (permit: foo@example.com) -> EE (SAN: bar@example.com)
+    ```
+
+    Per RFC 5280 4.2.1.10 an email name constraint that specifies a particular
+    mailbox permits only that mailbox, not other mailboxes on the same host.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.RFC822Name("foo@example.com")], excluded_subtrees=None
+            ),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        san=ext(x509.SubjectAlternativeName([x509.RFC822Name("bar@example.com")]), critical=False),
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_names(PeerName(kind="RFC822", value="bar@example.com"))
+        .fails()
+    )
+
+
+@testcase
+def nc_forbids_email_domain_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **invalid** graph:
+
+    ```
+    root -> ICA (permit: example.com) -> EE (SAN: foo@example.net)
+    ```
+
+    The EE's email address is on a host outside of the permitted
+    subtree, and must be rejected per RFC 5280 4.2.1.10.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[x509.RFC822Name("example.com")], excluded_subtrees=None
             ),
             critical=True,
         ),
         san=None,
     )
-
-    ica_b_key = ec.generate_private_key(ec.SECP256R1())
-    ica_b_1 = builder.intermediate_ca(
-        ica_a,
-        key=ica_b_key,
-        subject=x509.Name.from_rfc4514_string("CN=an-intermediate"),
-        san=ext(
-            x509.SubjectAlternativeName([x509.DNSName("forbidden.example.com")]), critical=False
-        ),
-    )
-    ica_b_2 = builder.intermediate_ca(
-        root_b,
-        key=ica_b_key,
-        subject=x509.Name.from_rfc4514_string("CN=an-intermediate"),
-        san=ext(
-            x509.SubjectAlternativeName([x509.DNSName("unconstrained-ica.example.com")]),
-            critical=False,
-        ),
-    )
-
     leaf = builder.leaf_cert(
-        ica_b_1,
-        subject=x509.Name.from_rfc4514_string("CN=unconstrained.example.com"),
-        san=ext(
-            x509.SubjectAlternativeName([x509.DNSName("unconstrained.example.com")]), critical=False
+        ica,
+        san=ext(x509.SubjectAlternativeName([x509.RFC822Name("foo@example.net")]), critical=False),
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
+            critical=False,
         ),
     )
 
     builder = (
-        builder.server_validation()
-        .trusted_certs(root_a, root_b)
-        .untrusted_intermediates(ica_a, ica_b_1, ica_b_2)
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
         .peer_certificate(leaf)
-        .expected_peer_name(PeerName(kind="DNS", value="unconstrained.example.com"))
-        .succeeds()
+        .expected_peer_names(PeerName(kind="RFC822", value="foo@example.net"))
+        .fails()
     )
 
 
 @testcase
-def nc_permits_email_exact(builder: Builder) -> None:
+def nc_forbids_email_host_subdomain(builder: Builder) -> None:
     """
-    Produces the following **valid** graph:
+    Produces the following **invalid** graph:
 
     ```
-    root -> ICA (permit: foo@example.com) -> EE (SAN: foo@example.com)
+    root -> ICA (permit: example.com) -> EE (SAN: foo@sub.example.com)
     ```
 
-    Per RFC 5280 4.2.1.10 an email name constraint may specify a particular mailbox,
-    like in this graph.
+    Per RFC 5280 4.2.1.10, an email name constraint without a leading period
+    specifies a particular host, rather than a domain. Mail addresses on
+    hosts within that domain do not satisfy the constraint:
+
+    > To indicate all Internet mail addresses on a particular host, the
+    > constraint is specified as the host name.  For example, the constraint
+    > "example.com" is satisfied by any mail address at the host
+    > "example.com".
     """
 
     root = builder.root_ca()
@@ -1543,7 +2306,7 @@ def nc_permits_email_exact(builder: Builder) -> None:
         root,
         name_constraints=ext(
             x509.NameConstraints(
-                permitted_subtrees=[x509.RFC822Name("foo@example.com")], excluded_subtrees=None
+                permitted_subtrees=[x509.RFC822Name("example.com")], excluded_subtrees=None
             ),
             critical=True,
         ),
@@ -1551,7 +2314,9 @@ def nc_permits_email_exact(builder: Builder) -> None:
     )
     leaf = builder.leaf_cert(
         ica,
-        san=ext(x509.SubjectAlternativeName([x509.RFC822Name("foo@example.com")]), critical=False),
+        san=ext(
+            x509.SubjectAlternativeName([x509.RFC822Name("foo@sub.example.com")]), critical=False
+        ),
         eku=ext(
             x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]),
             critical=False,
@@ -1564,22 +2329,30 @@ def nc_permits_email_exact(builder: Builder) -> None:
         .trusted_certs(root)
         .untrusted_intermediates(ica)
         .peer_certificate(leaf)
-        .expected_peer_names(PeerName(kind="RFC822", value="foo@example.com"))
-        .succeeds()
+        .expected_peer_names(PeerName(kind="RFC822", value="foo@sub.example.com"))
+        .fails()
     )
 
 
 @testcase
-def nc_permits_email_domain(builder: Builder) -> None:
+def nc_forbids_email_domain_apex(builder: Builder) -> None:
     """
-    Produces the following **valid** graph:
+    Produces the following **invalid** graph:
 
     ```
-    root -> ICA (permit: example.com) -> EE (SAN: foo@example.com)
+    root -> ICA (permit: .example.com) -> EE (SAN: foo@example.com)
     ```
 
-    Per RFC 5280 4.2.1.10 an email name constraint may specify a host name to
-    constrain all inboxes on that host.
+    Per RFC 5280 4.2.1.10, an email name constraint with a leading period
+    specifies the hosts within a domain, but not the domain's own host.
+    Mail addresses at the host `example.com` do not satisfy the constraint
+    `.example.com`:
+
+    > To indicate all Internet mail addresses on hosts in a
+    > particular domain, the constraint is specified as the domain name with a
+    > leading period (e.g., ".example.com").
+
+    This is the inverse of `nc-forbids-email-host-subdomain`.
     """
 
     root = builder.root_ca()
@@ -1587,7 +2360,7 @@ def nc_permits_email_domain(builder: Builder) -> None:
         root,
         name_constraints=ext(
             x509.NameConstraints(
-                permitted_subtrees=[x509.RFC822Name("example.com")], excluded_subtrees=None
+                permitted_subtrees=[x509.RFC822Name(".example.com")], excluded_subtrees=None
             ),
             critical=True,
         ),
@@ -1609,7 +2382,7 @@ def nc_permits_email_domain(builder: Builder) -> None:
         .untrusted_intermediates(ica)
         .peer_certificate(leaf)
         .expected_peer_names(PeerName(kind="RFC822", value="foo@example.com"))
-        .succeeds()
+        .fails()
     )
 
 
@@ -1719,3 +2492,277 @@ def nc_forbids_othername_noop(builder: Builder) -> None:
         .expected_peer_name(PeerName(kind="DNS", value="example.com"))
         .succeeds()
     )
+
+
+def _edi_party_name(party_name: str) -> bytes:
+    # NOTE: pyca/cryptography has no `EDIPartyName` general name, so we
+    # encode it by hand: `[5] { partyName [1] { UTF8String } }`.
+    return der_tlv(0xA5, der_tlv(0xA1, der_tlv(0x0C, party_name.encode())))
+
+
+def _edi_name_constraints(
+    *, permitted: list[bytes], excluded: list[bytes]
+) -> x509.UnrecognizedExtension:
+    def subtrees(tag: int, bases: list[bytes]) -> bytes:
+        return der_tlv(tag, b"".join(der_tlv(0x30, base) for base in bases)) if bases else b""
+
+    return x509.UnrecognizedExtension(
+        x509.ExtensionOID.NAME_CONSTRAINTS,
+        der_tlv(0x30, subtrees(0xA0, permitted) + subtrees(0xA1, excluded)),
+    )
+
+
+def _edi_san(*names: bytes) -> x509.UnrecognizedExtension:
+    return x509.UnrecognizedExtension(
+        x509.ExtensionOID.SUBJECT_ALTERNATIVE_NAME,
+        der_tlv(0x30, der_tlv(0x82, b"example.com") + b"".join(names)),
+    )
+
+
+@testcase
+def nc_permits_edipartyname(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root -> ICA (permit: dNSName, ediPartyName) -> EE (SAN: dNSName, ediPartyName)
+    ```
+
+    The ICA permits both `example.com` and an `ediPartyName` of `example`,
+    and the EE's SANs fall within both subtrees. Implementations that do not
+    support `ediPartyName` constraints must reject this chain instead, per
+    RFC 5280 4.2.1.10.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=None,
+        extra_extension=ext(
+            _edi_name_constraints(
+                permitted=[der_tlv(0x82, b"example.com"), _edi_party_name("example")],
+                excluded=[],
+            ),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        san=None,
+        extra_extension=ext(_edi_san(_edi_party_name("example")), critical=False),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.edi_party_name])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+        .succeeds()
+    )
+
+
+@testcase
+def nc_forbids_edipartyname(builder: Builder) -> None:
+    """
+    Produces the following **invalid** graph:
+
+    ```
+    root -> ICA (forbid: ediPartyName) -> EE (SAN: dNSName, ediPartyName)
+    ```
+
+    The ICA excludes an `ediPartyName` of `example`, which the EE's SAN
+    contains. This chain is invalid whether or not the implementation supports
+    `ediPartyName` constraints: implementations that do must reject the
+    excluded name, and implementations that don't must reject the
+    certificate, per RFC 5280 4.2.1.10:
+
+    > If a name constraints extension that is marked as critical
+    > imposes constraints on a particular name form, and an instance of
+    > that name form appears in the subject field or subjectAltName
+    > extension of a subsequent certificate, then the application MUST
+    > either process the constraint or reject the certificate.
+
+    Like the other `ediPartyName` testcases, this is marked with the
+    `edi-party-name` feature, so that harnesses that don't support
+    `ediPartyName` constraints skip it.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=None,
+        extra_extension=ext(
+            _edi_name_constraints(permitted=[], excluded=[_edi_party_name("example")]),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        san=None,
+        extra_extension=ext(_edi_san(_edi_party_name("example")), critical=False),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.edi_party_name])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+        .fails()
+    )
+
+
+@testcase
+def nc_forbids_edipartyname_noop(builder: Builder) -> None:
+    """
+    Produces the following **valid** graph:
+
+    ```
+    root -> ICA (forbid: ediPartyName) -> EE (SAN: dNSName)
+    ```
+
+    The ICA excludes an `ediPartyName`, but no certificate on the path has
+    an `ediPartyName`, so the constraint does not apply and the chain is valid.
+    Implementations that do not support `ediPartyName` constraints commonly
+    reject the ICA's name constraints outright instead.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=None,
+        extra_extension=ext(
+            _edi_name_constraints(permitted=[], excluded=[_edi_party_name("example")]),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.edi_party_name])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+        .succeeds()
+    )
+
+
+def _uri_nc(builder: Builder, *, permitted: str, uri: str) -> Builder:
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        name_constraints=ext(
+            x509.NameConstraints(
+                permitted_subtrees=[
+                    x509.DNSName("example.com"),
+                    x509.UniformResourceIdentifier(permitted),
+                ],
+                excluded_subtrees=None,
+            ),
+            critical=True,
+        ),
+        san=None,
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        san=ext(
+            x509.SubjectAlternativeName(
+                [x509.DNSName("example.com"), x509.UniformResourceIdentifier(uri)]
+            ),
+            critical=False,
+        ),
+    )
+
+    return (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+    )
+
+
+@testcase
+def nc_permits_uri_host(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA (permit: dNSName, URI) -> EE (SAN: dNSName, URI)
+    ```
+
+    The ICA permits URIs whose host is `example.com`, and the EE's URI SAN
+    is `https://example.com/index.html`. Per RFC 5280 4.2.1.10, a URI
+    constraint applies to the host part of the name:
+
+    > For URIs, the constraint applies to the host part of the name.  The
+    > constraint MUST be specified as a fully qualified domain name and MAY
+    > specify a host or a domain.
+    """
+
+    _uri_nc(builder, permitted="example.com", uri="https://example.com/index.html").succeeds()
+
+
+@testcase
+def nc_forbids_uri_host_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (permit: dNSName, URI) -> EE (SAN: dNSName, URI)
+    ```
+
+    The ICA permits URIs whose host is `example.com`, but the EE's URI SAN
+    is `https://example.net/index.html`, whose host is outside of the
+    permitted subtree.
+    """
+
+    _uri_nc(builder, permitted="example.com", uri="https://example.net/index.html").fails()
+
+
+@testcase
+def nc_permits_uri_domain(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA (permit: dNSName, URI) -> EE (SAN: dNSName, URI)
+    ```
+
+    The ICA permits URIs whose host is within `.example.com`, and the EE's
+    URI SAN is `https://foo.example.com/`. Per RFC 5280 4.2.1.10:
+
+    > When the constraint begins with a period, it MAY be expanded with one
+    > or more labels.  That is, the constraint ".example.com" is satisfied by
+    > both host.example.com and my.host.example.com.
+    """
+
+    _uri_nc(builder, permitted=".example.com", uri="https://foo.example.com/").succeeds()
+
+
+@testcase
+def nc_forbids_uri_domain_apex(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (permit: dNSName, URI) -> EE (SAN: dNSName, URI)
+    ```
+
+    The ICA permits URIs whose host is within `.example.com`, but the EE's
+    URI SAN is `https://example.com/`. Per RFC 5280 4.2.1.10, a constraint
+    with a leading period only matches subdomains:
+
+    > However, the constraint ".example.com" is not satisfied by
+    > "example.com".
+    """
+
+    _uri_nc(builder, permitted=".example.com", uri="https://example.com/").fails()
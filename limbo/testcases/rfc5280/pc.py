@@ -4,6 +4,7 @@ RFC 5280 Policy Constraints (PC) testcases.
 
 from cryptography import x509
 
+from limbo.assets import CertificatePair
 from limbo.models import Feature
 from limbo.testcases._core import Builder, ext, testcase
 
@@ -41,3 +42,424 @@ def ica_noncritical_pc(builder: Builder) -> None:
         .peer_certificate(leaf)
         .fails()
     )
+
+
+# An arbitrary certificate policy, under x509-limbo's PEN.
+_TEST_POLICY = x509.ObjectIdentifier("1.3.6.1.4.1.55738.666.2")
+
+
+@testcase
+def root_inhibit_any_policy(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root (policy X, requireExplicitPolicy:0, inhibitAnyPolicy:0)
+         -> ICA (anyPolicy) -> EE (policy X)
+    ```
+
+    The root carries a `PolicyConstraints` extension with
+    `requireExplicitPolicy` of 0 and an `inhibitAnyPolicy` extension with
+    `SkipCerts` of 0, which RFC 5937 3 allows a trust anchor to impose as the
+    initial `explicit_policy` and `inhibit_anyPolicy` inputs to path
+    validation. As a result, the ICA's `anyPolicy` assertion is not honored
+    and the valid policy tree is empty after the ICA.
+
+    The root itself asserts policy X, so that implementations that process
+    the trust anchor's own policies do not prune the tree early. Its policy
+    extensions are non-critical, so that validators that don't enforce
+    trust anchor constraints can still use it as a trust anchor.
+
+    Because the root requires an explicit policy, the chain is invalid for
+    validators that enforce trust anchor constraints.
+    """
+
+    root = builder.root_ca(
+        extra_extension=ext(
+            x509.CertificatePolicies([x509.PolicyInformation(_TEST_POLICY, None)]),
+            critical=False,
+        ),
+        extra_unchecked_extensions=[
+            ext(
+                x509.PolicyConstraints(require_explicit_policy=0, inhibit_policy_mapping=None),
+                critical=False,
+            ),
+            ext(x509.InhibitAnyPolicy(skip_certs=0), critical=False),
+        ],
+    )
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=ext(
+            x509.CertificatePolicies([x509.PolicyInformation(x509.OID_ANY_POLICY, None)]),
+            critical=False,
+        ),
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        extra_extension=ext(
+            x509.CertificatePolicies([x509.PolicyInformation(_TEST_POLICY, None)]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features(
+            [
+                Feature.has_policy_constraints,
+                Feature.has_cert_policies,
+                Feature.strict_anchor_constraints,
+            ]
+        )
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def root_inhibit_any_policy_no_any_policy(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root (policy X, requireExplicitPolicy:0, inhibitAnyPolicy:0)
+         -> ICA (policy X) -> EE (policy X)
+    ```
+
+    This is the same as `root-inhibit-any-policy`, except that the ICA
+    asserts an explicit policy rather than `anyPolicy`. The root's
+    `inhibitAnyPolicy` therefore has no effect, and its explicit policy
+    requirement is satisfied.
+    """
+
+    root = builder.root_ca(
+        extra_extension=ext(
+            x509.CertificatePolicies([x509.PolicyInformation(_TEST_POLICY, None)]),
+            critical=False,
+        ),
+        extra_unchecked_extensions=[
+            ext(
+                x509.PolicyConstraints(require_explicit_policy=0, inhibit_policy_mapping=None),
+                critical=False,
+            ),
+            ext(x509.InhibitAnyPolicy(skip_certs=0), critical=False),
+        ],
+    )
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=ext(
+            x509.CertificatePolicies([x509.PolicyInformation(_TEST_POLICY, None)]),
+            critical=False,
+        ),
+    )
+    leaf = builder.leaf_cert(
+        ica,
+        extra_extension=ext(
+            x509.CertificatePolicies([x509.PolicyInformation(_TEST_POLICY, None)]),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+def _inhibit_any_policy_chain(
+    builder: Builder, *, skip_certs: int, critical: bool, subordinates: int
+) -> tuple[CertificatePair, list[CertificatePair], CertificatePair]:
+    """
+    Produces a chain of `root -> ICA -> ... -> EE`, where the first ICA
+    requires an explicit policy and carries an `inhibitAnyPolicy` extension
+    with the given `SkipCerts`, followed by `subordinates` more ICAs. Every
+    ICA asserts only `anyPolicy`, and the EE asserts policy X.
+
+    Returns the root, the ICAs (in chain order), and the EE.
+    """
+
+    root = builder.root_ca()
+    any_policy = ext(
+        x509.CertificatePolicies([x509.PolicyInformation(x509.OID_ANY_POLICY, None)]),
+        critical=False,
+    )
+
+    icas = [
+        builder.intermediate_ca(
+            root,
+            extra_extension=any_policy,
+            extra_unchecked_extensions=[
+                ext(
+                    x509.PolicyConstraints(require_explicit_policy=0, inhibit_policy_mapping=None),
+                    critical=True,
+                ),
+                ext(x509.InhibitAnyPolicy(skip_certs=skip_certs), critical=critical),
+            ],
+        )
+    ]
+    for _ in range(subordinates):
+        icas.append(builder.intermediate_ca(icas[-1], extra_extension=any_policy))
+
+    leaf = builder.leaf_cert(
+        icas[-1],
+        extra_extension=ext(
+            x509.CertificatePolicies([x509.PolicyInformation(_TEST_POLICY, None)]),
+            critical=False,
+        ),
+    )
+
+    return root, icas, leaf
+
+
+@testcase
+def ica_inhibit_any_policy_skip_certs_0(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA1 (anyPolicy, inhibitAnyPolicy:0) -> ICA2 (anyPolicy) -> EE (policy X)
+    ```
+
+    ICA1's `inhibitAnyPolicy` has a `SkipCerts` of 0, so `anyPolicy` is
+    inhibited for every certificate after it (RFC 5280 4.2.1.14 and
+    6.1.4 (i)(2)). ICA2's `anyPolicy` assertion is therefore not honored,
+    and the valid policy tree is empty after ICA2.
+
+    ICA1 also requires an explicit policy, so the chain is invalid.
+    """
+
+    root, icas, leaf = _inhibit_any_policy_chain(
+        builder, skip_certs=0, critical=True, subordinates=1
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(*icas)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ica_inhibit_any_policy_skip_certs_1(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA1 (anyPolicy, inhibitAnyPolicy:1) -> ICA2 (anyPolicy) -> EE (policy X)
+    ```
+
+    ICA1's `inhibitAnyPolicy` has a `SkipCerts` of 1, so one more
+    certificate (ICA2) may use `anyPolicy` before it is inhibited. ICA2's
+    `anyPolicy` node is then matched by the EE's policy X, satisfying
+    ICA1's explicit policy requirement.
+    """
+
+    root, icas, leaf = _inhibit_any_policy_chain(
+        builder, skip_certs=1, critical=True, subordinates=1
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(*icas)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def ica_inhibit_any_policy_skip_certs_1_exceeded(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA1 (anyPolicy, inhibitAnyPolicy:1) -> ICA2 (anyPolicy)
+         -> ICA3 (anyPolicy) -> EE (policy X)
+    ```
+
+    This is the same as `ica-inhibit-any-policy-skip-certs-1`, except with
+    a second subordinate ICA. ICA1's `SkipCerts` of 1 allows ICA2 to use
+    `anyPolicy`, but not ICA3, so the valid policy tree is empty after ICA3
+    and ICA1's explicit policy requirement can't be satisfied.
+    """
+
+    root, icas, leaf = _inhibit_any_policy_chain(
+        builder, skip_certs=1, critical=True, subordinates=2
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(*icas)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ica_noncritical_inhibit_any_policy(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA1 (anyPolicy, inhibitAnyPolicy:1) -> ICA2 (anyPolicy) -> EE (policy X)
+    ```
+
+    This is the same as `ica-inhibit-any-policy-skip-certs-1`, except that
+    ICA1's `inhibitAnyPolicy` extension is marked as non-critical, which is
+    disallowed under RFC 5280 4.2.1.14:
+
+    > Conforming CAs MUST mark this extension as critical.
+    """
+
+    root, icas, leaf = _inhibit_any_policy_chain(
+        builder, skip_certs=1, critical=False, subordinates=1
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(*icas)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+def _require_explicit_policy_chain(
+    builder: Builder,
+    *,
+    require_explicit_policy: int,
+    ica_policy: bool,
+    ee_policy: bool,
+) -> tuple[CertificatePair, CertificatePair, CertificatePair]:
+    """
+    Produces a chain of `root -> ICA -> EE`, where the ICA has a
+    `PolicyConstraints` extension with the given `requireExplicitPolicy`,
+    and the ICA and EE each optionally assert policy X.
+    """
+
+    policies = ext(
+        x509.CertificatePolicies([x509.PolicyInformation(_TEST_POLICY, None)]),
+        critical=False,
+    )
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=policies if ica_policy else None,
+        extra_unchecked_extensions=[
+            ext(
+                x509.PolicyConstraints(
+                    require_explicit_policy=require_explicit_policy, inhibit_policy_mapping=None
+                ),
+                critical=True,
+            ),
+        ],
+    )
+    leaf = builder.leaf_cert(ica, extra_extension=policies if ee_policy else None)
+
+    return root, ica, leaf
+
+
+@testcase
+def ica_require_explicit_policy_0_ee_no_policy(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (policy X, requireExplicitPolicy:0) -> EE
+    ```
+
+    The ICA's `requireExplicitPolicy` of 0 requires an explicit policy for
+    the entire path from the ICA onwards (RFC 5280 6.1.4 (i)(1)). The EE
+    has no `certificatePolicies` extension, so the valid policy tree is
+    empty after the EE (RFC 5280 6.1.3 (e)) and the chain is invalid.
+    """
+
+    root, ica, leaf = _require_explicit_policy_chain(
+        builder, require_explicit_policy=0, ica_policy=True, ee_policy=False
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ica_require_explicit_policy_1(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (requireExplicitPolicy:1) -> EE (policy X)
+    ```
+
+    The ICA's `requireExplicitPolicy` of 1 allows one more certificate (the
+    EE) before an explicit policy is required for the entire path. The EE
+    is that certificate: RFC 5280 6.1.5 (a) counts it down, so
+    `explicit_policy` is 0 at the end of path validation.
+
+    The ICA asserts no policies, so the valid policy tree is already empty
+    after the ICA, and the EE's policy can't repopulate it. The chain is
+    therefore invalid.
+    """
+
+    root, ica, leaf = _require_explicit_policy_chain(
+        builder, require_explicit_policy=1, ica_policy=False, ee_policy=True
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ica_require_explicit_policy_2(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA (requireExplicitPolicy:2) -> EE (policy X)
+    ```
+
+    This is the same as `ica-require-explicit-policy-1`, except that the
+    ICA's `requireExplicitPolicy` of 2 allows two more certificates before
+    an explicit policy is required. The path ends after only one (the EE),
+    so `explicit_policy` is still 1 at the end of path validation, and the
+    empty valid policy tree is acceptable (RFC 5280 6.1.5 (g)).
+    """
+
+    root, ica, leaf = _require_explicit_policy_chain(
+        builder, require_explicit_policy=2, ica_policy=False, ee_policy=True
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
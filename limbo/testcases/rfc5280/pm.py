@@ -0,0 +1,197 @@
+"""
+RFC 5280 Policy Mappings (PM) testcases.
+"""
+
+from cryptography import x509
+
+from limbo.assets import der_tlv, ext
+from limbo.models import Feature
+from limbo.testcases._core import Builder, testcase
+
+# Two arbitrary certificate policies, under x509-limbo's PEN: the issuer's
+# domain policy, and the subject's domain policy that it maps to.
+_ISSUER_POLICY = x509.ObjectIdentifier("1.3.6.1.4.1.55738.666.2")
+_SUBJECT_POLICY = x509.ObjectIdentifier("1.3.6.1.4.1.55738.666.3")
+
+# The above policies and anyPolicy (2.5.29.32.0), DER-encoded.
+_ISSUER_POLICY_DER = bytes.fromhex("060b2b0601040183b33a851a02")
+_SUBJECT_POLICY_DER = bytes.fromhex("060b2b0601040183b33a851a03")
+_ANY_POLICY_DER = bytes.fromhex("0604551d2000")
+
+
+def _policy_mappings(issuer_policy: bytes, subject_policy: bytes) -> x509.UnrecognizedExtension:
+    """
+    Returns a `policyMappings` extension with a single mapping between the
+    given DER-encoded policy OIDs.
+
+    `cryptography` has no API for this extension, so it's encoded by hand.
+    """
+    mapping = der_tlv(0x30, issuer_policy + subject_policy)
+    return x509.UnrecognizedExtension(x509.ExtensionOID.POLICY_MAPPINGS, der_tlv(0x30, mapping))
+
+
+def _policies(policy: x509.ObjectIdentifier) -> x509.CertificatePolicies:
+    return x509.CertificatePolicies([x509.PolicyInformation(policy, None)])
+
+
+def _require_explicit_policy(
+    *, inhibit_policy_mapping: int | None = None
+) -> x509.PolicyConstraints:
+    return x509.PolicyConstraints(
+        require_explicit_policy=0, inhibit_policy_mapping=inhibit_policy_mapping
+    )
+
+
+@testcase
+def ica_maps_issuer_policy(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA (policy X, X => Y) -> EE (policy Y)
+    ```
+
+    The ICA asserts policy X and maps it to policy Y in its subjects'
+    domain (RFC 5280 4.2.1.5). The EE asserts policy Y, which RFC 5280
+    6.1.3 (d)(1) matches against the ICA's mapped policy, so the valid
+    policy tree contains X and satisfies the ICA's explicit policy
+    requirement.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=ext(_policies(_ISSUER_POLICY), critical=False),
+        extra_unchecked_extensions=[
+            ext(_policy_mappings(_ISSUER_POLICY_DER, _SUBJECT_POLICY_DER), critical=True),
+            ext(_require_explicit_policy(), critical=True),
+        ],
+    )
+    leaf = builder.leaf_cert(ica, extra_extension=ext(_policies(_SUBJECT_POLICY), critical=False))
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
+
+
+@testcase
+def ica_maps_from_any_policy(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (policy X, anyPolicy => Y) -> EE (policy Y)
+    ```
+
+    The ICA's `policyMappings` extension maps `anyPolicy` to policy Y,
+    which is forbidden under RFC 5280 4.2.1.5:
+
+    > Policies MUST NOT be mapped either to or from the special value
+    > anyPolicy (anyPolicy) [...]
+
+    RFC 5280 6.1.4 (a) requires path validation to fail in this case.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=ext(_policies(_ISSUER_POLICY), critical=False),
+        extra_unchecked_extensions=[
+            ext(_policy_mappings(_ANY_POLICY_DER, _SUBJECT_POLICY_DER), critical=True),
+            ext(_require_explicit_policy(), critical=True),
+        ],
+    )
+    leaf = builder.leaf_cert(ica, extra_extension=ext(_policies(_SUBJECT_POLICY), critical=False))
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ica_maps_to_any_policy(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA (policy X, X => anyPolicy) -> EE (policy Y)
+    ```
+
+    The ICA's `policyMappings` extension maps policy X to `anyPolicy`,
+    which is forbidden under RFC 5280 4.2.1.5 and 6.1.4 (a) for the same
+    reason as `ica-maps-from-any-policy`.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=ext(_policies(_ISSUER_POLICY), critical=False),
+        extra_unchecked_extensions=[
+            ext(_policy_mappings(_ISSUER_POLICY_DER, _ANY_POLICY_DER), critical=True),
+            ext(_require_explicit_policy(), critical=True),
+        ],
+    )
+    leaf = builder.leaf_cert(ica, extra_extension=ext(_policies(_SUBJECT_POLICY), critical=False))
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def inhibit_policy_mapping(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA1 (policy X, inhibitPolicyMapping:0) -> ICA2 (policy X, X => Y) -> EE (policy Y)
+    ```
+
+    ICA1's `PolicyConstraints` sets `inhibitPolicyMapping` to 0, so policy
+    mapping is inhibited for every certificate after it. Per RFC 5280
+    6.1.4 (b)(2), ICA2's mapping of X therefore deletes X from the valid
+    policy tree rather than mapping it to Y, leaving the tree empty.
+
+    ICA1 also requires an explicit policy, so the chain is invalid.
+    """
+
+    root = builder.root_ca()
+    ica1 = builder.intermediate_ca(
+        root,
+        extra_extension=ext(_policies(_ISSUER_POLICY), critical=False),
+        extra_unchecked_extensions=[
+            ext(_require_explicit_policy(inhibit_policy_mapping=0), critical=True),
+        ],
+    )
+    ica2 = builder.intermediate_ca(
+        ica1,
+        extra_extension=ext(_policies(_ISSUER_POLICY), critical=False),
+        extra_unchecked_extensions=[
+            ext(_policy_mappings(_ISSUER_POLICY_DER, _SUBJECT_POLICY_DER), critical=True),
+        ],
+    )
+    leaf = builder.leaf_cert(ica2, extra_extension=ext(_policies(_SUBJECT_POLICY), critical=False))
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.has_policy_constraints, Feature.has_cert_policies])
+        .trusted_certs(root)
+        .untrusted_intermediates(ica1, ica2)
+        .peer_certificate(leaf)
+        .fails()
+    )
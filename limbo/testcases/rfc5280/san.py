@@ -4,8 +4,8 @@ RFC 5280 Subject Alternative Name (SAN) testcases.
 
 from cryptography import x509
 
-from limbo.assets import ext
-from limbo.models import PeerName
+from limbo.assets import der_tlv, ext
+from limbo.models import KnownEKUs, PeerName
 from limbo.testcases._core import Builder, testcase
 
 
@@ -56,3 +56,116 @@ def noncritical_with_empty_subject(builder: Builder) -> None:
     builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
         PeerName(kind="DNS", value="example.com")
     ).fails()
+
+
+# Microsoft's User Principal Name (UPN) OtherName, as used in smart card
+# logon certificates. Its value is a UTF8String.
+_UPN_OID = x509.ObjectIdentifier("1.3.6.1.4.1.311.20.2.3")
+
+# RFC 4985's SRVName OtherName. Its value is an IA5String.
+_SRV_NAME_OID = x509.ObjectIdentifier("1.3.6.1.5.5.7.8.7")
+
+
+def _upn(value: str) -> x509.OtherName:
+    return x509.OtherName(_UPN_OID, der_tlv(0x0C, value.encode()))
+
+
+@testcase
+def upn_othername(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's SAN contains only a User Principal Name (UPN) OtherName
+    (OID 1.3.6.1.4.1.311.20.2.3), as is common in smart card client
+    certificates, which matches the expected UPN peer name.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        subject=x509.Name.from_rfc4514_string("CN=alice"),
+        san=ext(x509.SubjectAlternativeName([_upn("alice@example.com")]), critical=False),
+        eku=ext(x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]), critical=False),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="UPN", value="alice@example.com"))
+        .succeeds()
+    )
+
+
+@testcase
+def upn_othername_mismatch(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    This is the same as `upn-othername`, except that the EE's UPN is
+    `bob@example.com` rather than the expected `alice@example.com`.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        subject=x509.Name.from_rfc4514_string("CN=bob"),
+        san=ext(x509.SubjectAlternativeName([_upn("bob@example.com")]), critical=False),
+        eku=ext(x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]), critical=False),
+    )
+
+    builder = (
+        builder.client_validation()
+        .extended_key_usage([KnownEKUs.client_auth])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="UPN", value="alice@example.com"))
+        .fails()
+    )
+
+
+@testcase
+def srv_othername_with_dns(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's SAN contains a `dNSName` matching the expected peer name, along
+    with an RFC 4985 SRVName OtherName (OID 1.3.6.1.5.5.7.8.7). Validators
+    that don't recognize the OtherName should ignore it and match the
+    `dNSName` as usual, since the SAN extension isn't critical.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName(
+                [
+                    x509.DNSName("example.com"),
+                    x509.OtherName(_SRV_NAME_OID, der_tlv(0x16, b"_https.example.com")),
+                ]
+            ),
+            critical=False,
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+        .succeeds()
+    )
@@ -0,0 +1,150 @@
+"""
+RFC 5280 unique identifier (`issuerUniqueID` and `subjectUniqueID`) testcases.
+
+RFC 5280 4.1.2.8 forbids conforming CAs from generating certificates with
+unique identifiers, but says that applications SHOULD be capable of parsing
+them and places no processing requirements on them. Unique identifiers
+require a v2 (or v3) certificate; these testcases use v2 certificates.
+"""
+
+from limbo.assets import TBS_EXTENSIONS_INDEX, TBS_VERSION_INDEX, der_tlv, tamper
+from limbo.models import CertProfile, Feature, Importance
+from limbo.testcases._core import Builder, testcase
+
+
+def _v2_with_unique_ids(fields: list[bytes]) -> list[bytes]:
+    """
+    Rewrites the fields of a v3 `tbsCertificate` into a v2 one: the version
+    becomes v2, the extensions are dropped, and an `issuerUniqueID` and
+    `subjectUniqueID` are added in their place.
+    """
+
+    # version [0] EXPLICIT INTEGER { v2(1) }
+    fields[TBS_VERSION_INDEX] = der_tlv(0xA0, der_tlv(0x02, b"\x01"))
+    # issuerUniqueID [1] IMPLICIT BIT STRING, subjectUniqueID [2] IMPLICIT BIT STRING
+    fields[TBS_EXTENSIONS_INDEX] = der_tlv(0x81, b"\x00\xca\xfe")
+    fields.append(der_tlv(0x82, b"\x00\xbe\xef"))
+    return fields
+
+
+@testcase
+def ee_v2_unique_ids(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is a v2 certificate with both an `issuerUniqueID` and a
+    `subjectUniqueID`, and without any extensions. Validators that reject
+    v2 certificates must reject this chain.
+
+    This is the strict variant of `ee-v2-unique-ids-allowed`.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    tampered = tamper(leaf, root.key, tbs=_v2_with_unique_ids)
+
+    builder = (
+        builder.features([Feature.reject_v2])
+        .conflicts_with("rfc5280::uid::ee-v2-unique-ids-allowed")
+        .client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .expected_peer_name(None)
+        .fails()
+    )
+
+
+@testcase
+def ee_v2_unique_ids_allowed(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    This is the same as `ee-v2-unique-ids`, but RFC 5280 places no processing
+    requirements on unique identifiers, and nothing in RFC 5280 6.1 requires
+    an EE to be a v3 certificate. Validators that accept v2 certificates
+    should accept this chain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    tampered = tamper(leaf, root.key, tbs=_v2_with_unique_ids)
+
+    builder = (
+        builder.importance(Importance.LOW)
+        .conflicts_with("rfc5280::uid::ee-v2-unique-ids")
+        .client_validation()
+        .profile(CertProfile.GENERIC)
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .expected_peer_name(None)
+        .succeeds()
+    )
+
+
+@testcase
+def root_v2_unique_ids(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is well-formed, but the root is a v2 certificate with both an
+    `issuerUniqueID` and a `subjectUniqueID`, and without any extensions.
+    Validators that reject v2 certificates, even as trust anchors, must
+    reject this chain.
+
+    This is the strict variant of `root-v2-unique-ids-trusted`.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    tampered = tamper(root, root.key, tbs=_v2_with_unique_ids)
+
+    builder = (
+        builder.features([Feature.reject_v2])
+        .conflicts_with("rfc5280::uid::root-v2-unique-ids-trusted")
+        .server_validation()
+        .trusted_certs(tampered)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def root_v2_unique_ids_trusted(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    This is the same as `root-v2-unique-ids`, but RFC 5280 6.1.1 (d) only
+    uses a trust anchor's name and public key, and RFC 5937 doesn't require
+    trust anchors to be v3 certificates. Validators that accept v2 trust
+    anchors should accept this chain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    tampered = tamper(root, root.key, tbs=_v2_with_unique_ids)
+
+    builder = (
+        builder.importance(Importance.UNDETERMINED)
+        .conflicts_with("rfc5280::uid::root-v2-unique-ids")
+        .server_validation()
+        .trusted_certs(tampered)
+        .peer_certificate(leaf)
+        .succeeds()
+    )
@@ -3,11 +3,25 @@ RFC 5280 validity testcases.
 """
 
 from datetime import datetime, timedelta
+from typing import Callable
 
-from limbo.models import Importance
+from limbo.assets import TBS_VALIDITY_INDEX, der_tlv, replace_tbs_field, tamper
+from limbo.models import Feature, Importance
 from limbo.testcases._core import Builder, testcase
 
 
+def _utc_time(time: str) -> bytes:
+    return der_tlv(0x17, time.encode())
+
+
+def _generalized_time(time: str) -> bytes:
+    return der_tlv(0x18, time.encode())
+
+
+def _with_validity(not_before: bytes, not_after: bytes) -> Callable[[list[bytes]], list[bytes]]:
+    return replace_tbs_field(TBS_VALIDITY_INDEX, der_tlv(0x30, not_before + not_after))
+
+
 @testcase
 def expired_root(builder: Builder) -> None:
     """
@@ -19,6 +33,10 @@ def expired_root(builder: Builder) -> None:
 
     All three certificates are well-formed, but the root
     (and only the root) is expired at the validation time.
+
+    RFC 5937 4 permits, but does not require, relying parties to reject
+    trust anchors that are outside of their validity period. This is the
+    strict variant of `expired-root-trusted`.
     """
 
     # Root is valid from 2016 to 2020.
@@ -44,6 +62,7 @@ def expired_root(builder: Builder) -> None:
     builder = (
         builder.server_validation()
         .importance(Importance.UNDETERMINED)
+        .features([Feature.strict_anchor_validity])
         .trusted_certs(root)
         .untrusted_intermediates(intermediate)
         .peer_certificate(leaf)
@@ -54,6 +73,59 @@ def expired_root(builder: Builder) -> None:
     )
 
 
+@testcase
+def expired_root_trusted(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> intermediate -> EE
+    ```
+
+    This is the same as `expired-root`, but the relying party treats
+    the root as a trust anchor regardless of its validity period.
+
+    RFC 5280 6.1 describes a trust anchor as an input to path validation
+    rather than as a certificate in the path, and many validators (such as
+    webpki) never check its validity. Others (such as OpenSSL and Go)
+    reject an expired root. Enforcing the anchor's validity can catch
+    stale trust stores, but it also breaks chains that are otherwise
+    valid through no fault of the peer.
+    """
+
+    # Root is valid from 2016 to 2020.
+    root = builder.root_ca(
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2020-01-01T00:00:00Z"),
+    )
+
+    # Intermediate is valid from 2016 to 2026.
+    intermediate = builder.intermediate_ca(
+        root,
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Leaf is valid from 2018 to 2023.
+    leaf = builder.leaf_cert(
+        intermediate,
+        not_before=datetime.fromisoformat("2018-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2023-01-01T00:00:00Z"),
+    )
+
+    builder = (
+        builder.server_validation()
+        .importance(Importance.UNDETERMINED)
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is valid for the intermediate and leaf
+        # but not the root.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .succeeds()
+    )
+
+
 @testcase
 def expired_intermediate(builder: Builder) -> None:
     """
@@ -65,6 +137,9 @@ def expired_intermediate(builder: Builder) -> None:
 
     All three certificates are well-formed, but the intermediate
     (and only the intermediate) is expired at the validation time.
+
+    The leaf being valid doesn't make up for the broken link: RFC 5280 6.1.3
+    requires each certificate in the path to be valid at the validation time.
     """
 
     # Root is valid from 2016 to 2026.
@@ -111,6 +186,16 @@ def expired_leaf(builder: Builder) -> None:
 
     All three certificates are well-formed, but the leaf
     (and only the leaf) is expired at the validation time.
+
+    RFC 5280 6.1.3 requires every certificate in the path, including the
+    EE, to be valid at the validation time:
+
+    > (a)  Verify the basic certificate information.  The certificate
+    >      MUST satisfy each of the following:
+    >
+    >      ...
+    >
+    >      (2)  The certificate validity period includes the current time.
     """
 
     # Root is valid from 2016 to 2026.
@@ -136,16 +221,256 @@ def expired_leaf(builder: Builder) -> None:
     builder = (
         builder.server_validation()
         .importance(Importance.CRITICAL)
+        .conflicts_with("rfc5280::validity::expired-leaf-allowed")
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is valid for the root and intermediate
+        # but not the leaf.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .fails()
+    )
+
+
+@testcase
+def expired_leaf_allowed(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> intermediate -> EE
+    ```
+
+    This is the same as `expired-leaf`, but the relying party tolerates an
+    expired EE, as some do when validating archived signatures that were made
+    while the EE was still valid. This is **not** RFC 5280 conformant.
+    """
+
+    # Root is valid from 2016 to 2026.
+    root = builder.root_ca(
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Intermediate is valid from 2016 to 2026.
+    intermediate = builder.intermediate_ca(
+        root,
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Leaf is valid from 2018 to 2021.
+    leaf = builder.leaf_cert(
+        intermediate,
+        not_before=datetime.fromisoformat("2018-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2021-01-01T00:00:00Z"),
+    )
+
+    builder = (
+        builder.server_validation()
+        .conflicts_with("rfc5280::validity::expired-leaf")
+        .features([Feature.allow_expired_leaf])
         .trusted_certs(root)
         .untrusted_intermediates(intermediate)
         .peer_certificate(leaf)
         # We validate in 2022, which is valid for the root and intermediate
         # but not the leaf.
         .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .succeeds()
+    )
+
+
+@testcase
+def not_yet_valid_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> intermediate -> EE
+    ```
+
+    All three certificates are well-formed, but the intermediate
+    (and only the intermediate) is not yet valid at the validation time.
+    """
+
+    # Root is valid from 2016 to 2026.
+    root = builder.root_ca(
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Intermediate is valid from 2023 to 2026.
+    intermediate = builder.intermediate_ca(
+        root,
+        not_before=datetime.fromisoformat("2023-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Leaf is valid from 2018 to 2025.
+    leaf = builder.leaf_cert(
+        intermediate,
+        not_before=datetime.fromisoformat("2018-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2025-01-01T00:00:00Z"),
+    )
+
+    builder = (
+        builder.server_validation()
+        .importance(Importance.CRITICAL)
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is valid for the root and leaf
+        # but not yet for the intermediate.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
         .fails()
     )
 
 
+@testcase
+def intermediate_notbefore_exact(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> intermediate -> EE
+    ```
+
+    The intermediate becomes valid at `2022-01-01T00:00:00Z`, and the chain
+    is validated at exactly that time. The root and leaf are already valid.
+
+    RFC 5280 4.1.2.5 says that `notBefore` is inclusive, so this chain should
+    validate. This is the complement of `not-yet-valid-intermediate`.
+    """
+
+    # Root is valid from 2016 to 2026.
+    root = builder.root_ca(
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Intermediate is valid from 2022 to 2026.
+    intermediate = builder.intermediate_ca(
+        root,
+        not_before=datetime.fromisoformat("2022-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Leaf is valid from 2018 to 2025.
+    leaf = builder.leaf_cert(
+        intermediate,
+        not_before=datetime.fromisoformat("2018-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2025-01-01T00:00:00Z"),
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .succeeds()
+    )
+
+
+@testcase
+def not_yet_valid_root(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> intermediate -> EE
+    ```
+
+    All three certificates are well-formed, but the root
+    (and only the root) is not yet valid at the validation time.
+
+    Like `expired-root`, this is the strict variant of
+    `not-yet-valid-root-trusted`: RFC 5937 4 permits, but does not require,
+    relying parties to reject trust anchors that are outside of their
+    validity period.
+    """
+
+    # Root is valid from 2023 to 2030.
+    root = builder.root_ca(
+        not_before=datetime.fromisoformat("2023-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2030-01-01T00:00:00Z"),
+    )
+
+    # Intermediate is valid from 2016 to 2026.
+    intermediate = builder.intermediate_ca(
+        root,
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Leaf is valid from 2018 to 2023.
+    leaf = builder.leaf_cert(
+        intermediate,
+        not_before=datetime.fromisoformat("2018-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2023-01-01T00:00:00Z"),
+    )
+
+    builder = (
+        builder.server_validation()
+        .importance(Importance.UNDETERMINED)
+        .features([Feature.strict_anchor_validity])
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is valid for the intermediate and leaf
+        # but not yet for the root.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .fails()
+    )
+
+
+@testcase
+def not_yet_valid_root_trusted(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> intermediate -> EE
+    ```
+
+    This is the same as `not-yet-valid-root`, but the relying party treats
+    the root as a trust anchor regardless of its validity period, as with
+    `expired-root-trusted`.
+    """
+
+    # Root is valid from 2023 to 2030.
+    root = builder.root_ca(
+        not_before=datetime.fromisoformat("2023-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2030-01-01T00:00:00Z"),
+    )
+
+    # Intermediate is valid from 2016 to 2026.
+    intermediate = builder.intermediate_ca(
+        root,
+        not_before=datetime.fromisoformat("2016-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2026-01-01T00:00:00Z"),
+    )
+
+    # Leaf is valid from 2018 to 2023.
+    leaf = builder.leaf_cert(
+        intermediate,
+        not_before=datetime.fromisoformat("2018-01-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2023-01-01T00:00:00Z"),
+    )
+
+    builder = (
+        builder.server_validation()
+        .importance(Importance.UNDETERMINED)
+        .trusted_certs(root)
+        .untrusted_intermediates(intermediate)
+        .peer_certificate(leaf)
+        # We validate in 2022, which is valid for the intermediate and leaf
+        # but not yet for the root.
+        .validation_time(datetime.fromisoformat("2022-01-01T00:00:00Z"))
+        .succeeds()
+    )
+
+
 @testcase
 def notbefore_exact(builder: Builder) -> None:
     """
@@ -224,6 +549,44 @@ def notbefore_fractional(builder: Builder) -> None:
     )
 
 
+@testcase
+def notbefore_one_second_before(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    EE becomes valid at `2024-03-01T00:00:00Z`, and the chain is validated at
+    `2024-02-29T23:59:59Z`, i.e. one second before the EE's `notBefore`.
+    The root and ICA are valid at the validation time.
+
+    This exercises the lower bound of the validity period, which is a
+    common source of off-by-one errors.
+    """
+
+    not_before = datetime.fromisoformat("2024-03-01T00:00:00Z")
+    not_after = datetime.fromisoformat("2024-04-01T00:00:00Z")
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(
+        ica,
+        not_before=not_before,
+        not_after=not_after,
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .validation_time(not_before - timedelta(seconds=1))
+        .fails()
+    )
+
+
 @testcase
 def notafter_exact(builder: Builder) -> None:
     """
@@ -317,3 +680,180 @@ def notafter_fractional(builder: Builder) -> None:
         .validation_time(not_after + timedelta(milliseconds=5))
         .succeeds()
     )
+
+
+@testcase
+def notafter_one_second_after(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    EE expires at `2024-04-01T00:00:00Z`, and the chain is validated at
+    `2024-04-01T00:00:01Z`, i.e. one second after the EE's `notAfter`.
+    The root and ICA are valid at the validation time.
+
+    This exercises the upper bound of the validity period, which is a
+    common source of off-by-one errors.
+    """
+
+    not_before = datetime.fromisoformat("2024-03-01T00:00:00Z")
+    not_after = datetime.fromisoformat("2024-04-01T00:00:00Z")
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(
+        ica,
+        not_before=not_before,
+        not_after=not_after,
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .validation_time(not_after + timedelta(seconds=1))
+        .fails()
+    )
+
+
+@testcase
+def validity_utctime(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's validity period falls between 1950 and 2049, and is correctly
+    encoded with `UTCTime` as required by RFC 5280 4.1.2.5:
+
+    > CAs conforming to this profile MUST always encode certificate
+    > validity dates through the year 2049 as UTCTime; certificate validity
+    > dates in 2050 or later MUST be encoded as GeneralizedTime.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        not_before=datetime.fromisoformat("2024-03-01T00:00:00Z"),
+        not_after=datetime.fromisoformat("2025-03-01T00:00:00Z"),
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .validation_time(datetime.fromisoformat("2024-06-01T00:00:00Z"))
+        .succeeds()
+    )
+
+
+@testcase
+def validity_generalizedtime_before_2050(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's `notBefore` is in 2024 but is encoded as a `GeneralizedTime`,
+    rather than the `UTCTime` required by RFC 5280 4.1.2.5 for dates
+    through the year 2049.
+
+    The encoded time itself is unambiguous, so this is a "pedantic" check.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    tampered = tamper(
+        leaf,
+        root.key,
+        tbs=_with_validity(_generalized_time("20240301000000Z"), _utc_time("250301000000Z")),
+    )
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.pedantic_rfc5280])
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .validation_time(datetime.fromisoformat("2024-06-01T00:00:00Z"))
+        .fails()
+    )
+
+
+@testcase
+def validity_utctime_after_2049(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's `notAfter` is intended to be `2050-03-01T00:00:00Z`, but is
+    encoded as a `UTCTime` rather than the `GeneralizedTime` required by
+    RFC 5280 4.1.2.5 for dates in 2050 or later.
+
+    Per RFC 5280 4.1.2.5.1, a two-digit year of `50` is interpreted as
+    `1950`, so the EE's `notAfter` actually precedes its `notBefore` and the
+    EE is never valid.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    tampered = tamper(
+        leaf,
+        root.key,
+        tbs=_with_validity(_utc_time("240301000000Z"), _utc_time("500301000000Z")),
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .validation_time(datetime.fromisoformat("2024-06-01T00:00:00Z"))
+        .fails()
+    )
+
+
+@testcase
+def validity_generalizedtime_offset(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's `notBefore` is a `GeneralizedTime` with a timezone offset
+    (`+0100`), which RFC 5280 4.1.2.5.2 forbids:
+
+    > For the purposes of this profile, GeneralizedTime values MUST be
+    > expressed in Greenwich Mean Time (Zulu) and MUST include seconds
+    > (i.e., times are YYYYMMDDHHMMSSZ), even where the number of seconds
+    > is zero.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    tampered = tamper(
+        leaf,
+        root.key,
+        tbs=_with_validity(
+            _generalized_time("20240301000000+0100"), _generalized_time("20500301000000Z")
+        ),
+    )
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(tampered)
+        .validation_time(datetime.fromisoformat("2024-06-01T00:00:00Z"))
+        .fails()
+    )
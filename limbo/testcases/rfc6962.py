@@ -0,0 +1,171 @@
+"""
+RFC 6962 (Certificate Transparency) testcases.
+"""
+
+import hashlib
+import struct
+
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+
+from limbo.assets import (
+    TBS_EXTENSIONS_INDEX,
+    CertificatePair,
+    RawCertificate,
+    der_children,
+    der_tlv,
+    tamper,
+)
+from limbo.models import Feature
+from limbo.testcases._core import Builder, testcase
+
+# id-ce-embeddedSCT (1.3.6.1.4.1.11129.2.4.2), DER-encoded.
+_EMBEDDED_SCT_OID = bytes.fromhex("060a2b06010401d679020402")
+
+# An arbitrary timestamp (2024-03-01T00:00:00Z), in milliseconds.
+_SCT_TIMESTAMP = 1709251200000
+
+
+def _with_embedded_sct(
+    leaf: CertificatePair, issuer: CertificatePair, *, corrupt_signature: bool = False
+) -> RawCertificate:
+    """
+    Embeds a single SCT from a throwaway P-256 "log" into `leaf`,
+    returning the re-signed certificate.
+    """
+    log_key = ec.generate_private_key(ec.SECP256R1())
+    log_id = hashlib.sha256(
+        log_key.public_key().public_bytes(
+            serialization.Encoding.DER, serialization.PublicFormat.SubjectPublicKeyInfo
+        )
+    ).digest()
+    issuer_key_hash = hashlib.sha256(
+        issuer.cert.public_key().public_bytes(
+            serialization.Encoding.DER, serialization.PublicFormat.SubjectPublicKeyInfo
+        )
+    ).digest()
+
+    # RFC 6962 3.2: the SCT signs over the precertificate's TBSCertificate,
+    # i.e. the final TBSCertificate without the SCT list extension.
+    precert_tbs = leaf.cert.tbs_certificate_bytes
+    signed = (
+        # sct_version (v1), signature_type (certificate_timestamp)
+        b"\x00\x00"
+        + struct.pack(">Q", _SCT_TIMESTAMP)
+        # entry_type (precert_entry)
+        + struct.pack(">H", 1)
+        + issuer_key_hash
+        + struct.pack(">I", len(precert_tbs))[1:]
+        + precert_tbs
+        # extensions (empty)
+        + struct.pack(">H", 0)
+    )
+    if corrupt_signature:
+        signed = b"not the signed data"
+    signature = log_key.sign(signed, ec.ECDSA(hashes.SHA256()))
+
+    sct = (
+        b"\x00"
+        + log_id
+        + struct.pack(">Q", _SCT_TIMESTAMP)
+        + struct.pack(">H", 0)
+        # hash (sha256), signature (ecdsa)
+        + b"\x04\x03"
+        + struct.pack(">H", len(signature))
+        + signature
+    )
+    sct_list = struct.pack(">H", len(sct) + 2) + struct.pack(">H", len(sct)) + sct
+    extension = der_tlv(0x30, _EMBEDDED_SCT_OID + der_tlv(0x04, der_tlv(0x04, sct_list)))
+
+    def _tbs(fields: list[bytes]) -> list[bytes]:
+        # extensions [3] EXPLICIT SEQUENCE OF Extension
+        (extensions,) = der_children(fields[TBS_EXTENSIONS_INDEX])
+        fields[TBS_EXTENSIONS_INDEX] = der_tlv(
+            0xA3, der_tlv(0x30, b"".join(der_children(extensions)) + extension)
+        )
+        return fields
+
+    return tamper(leaf, issuer.key, tbs=_tbs)
+
+
+@testcase
+def ee_embedded_sct(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE contains an embedded SCT list extension
+    (OID 1.3.6.1.4.1.11129.2.4.2) with a single well-formed SCT. The SCT is
+    validly signed, but by a log that no implementation will recognize.
+
+    Implementations that don't enforce Certificate Transparency should
+    ignore this extension, since it isn't marked critical.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(_with_embedded_sct(leaf, root))
+        .succeeds()
+    )
+
+
+@testcase
+def ee_embedded_sct_invalid_signature(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE contains an embedded SCT list extension with a single SCT whose
+    signature does not cover the EE's precertificate data.
+
+    Implementations that enforce Certificate Transparency should reject this
+    chain, since the EE has no valid SCTs.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.require_embedded_sct])
+        .trusted_certs(root)
+        .peer_certificate(_with_embedded_sct(leaf, root, corrupt_signature=True))
+        .fails()
+    )
+
+
+@testcase
+def ee_missing_sct(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE contains no embedded SCTs.
+
+    Implementations that enforce Certificate Transparency should reject this
+    chain, since the EE has no SCTs.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    builder = (
+        builder.server_validation()
+        .features([Feature.require_embedded_sct])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .fails()
+    )
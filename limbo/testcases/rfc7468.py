@@ -0,0 +1,149 @@
+"""
+RFC 7468 (PEM) testcases.
+
+These testcases supply certificates (as the peer, a trust anchor, or an
+intermediate) that aren't well-formed PEM certificates, which a validator
+(or its harness) must reject rather than crash on.
+"""
+
+from cryptography.hazmat.primitives import serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+
+from limbo.assets import RawPEM
+from limbo.testcases._core import Builder, testcase
+
+
+@testcase
+def ee_empty(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> [EE]
+    ```
+
+    The peer certificate is an empty string.
+    """
+
+    root = builder.root_ca()
+
+    builder = (
+        builder.server_validation().trusted_certs(root).peer_certificate(RawPEM("")).fails()
+    )
+
+
+@testcase
+def ee_invalid_base64(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> [EE]
+    ```
+
+    The peer certificate has well-formed PEM encapsulation boundaries, but
+    its body isn't valid base64, as RFC 7468 3 requires.
+    """
+
+    root = builder.root_ca()
+    pem = RawPEM("-----BEGIN CERTIFICATE-----\n!!!!not*base64!!!!\n-----END CERTIFICATE-----\n")
+
+    builder = builder.server_validation().trusted_certs(root).peer_certificate(pem).fails()
+
+
+@testcase
+def ee_wrong_label(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> [EE]
+    ```
+
+    The peer certificate is a well-formed PEM block, but it's labeled
+    `PRIVATE KEY` and contains a PKCS#8 private key rather than a
+    certificate. RFC 7468 5 requires certificates to be labeled
+    `CERTIFICATE`.
+    """
+
+    root = builder.root_ca()
+    key = ec.generate_private_key(ec.SECP256R1())
+    pem = key.private_bytes(
+        encoding=serialization.Encoding.PEM,
+        format=serialization.PrivateFormat.PKCS8,
+        encryption_algorithm=serialization.NoEncryption(),
+    ).decode()
+
+    builder = (
+        builder.server_validation().trusted_certs(root).peer_certificate(RawPEM(pem)).fails()
+    )
+
+
+@testcase
+def ee_missing_boundaries(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The peer certificate is an otherwise valid EE, but it's supplied as the
+    bare base64 encoding of its DER, without the PEM encapsulation
+    boundaries that RFC 7468 2 requires.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    body = "".join(leaf.cert_pem.splitlines()[1:-1])
+
+    builder = (
+        builder.server_validation().trusted_certs(root).peer_certificate(RawPEM(body)).fails()
+    )
+
+
+@testcase
+def trusted_invalid_base64(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    [root] -> EE
+    ```
+
+    The EE is well-formed, but its issuing root is supplied as a trusted
+    certificate whose PEM body isn't valid base64.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+    pem = RawPEM("-----BEGIN CERTIFICATE-----\n!!!!not*base64!!!!\n-----END CERTIFICATE-----\n")
+
+    builder = builder.server_validation().trusted_certs(pem).peer_certificate(leaf).fails()
+
+
+@testcase
+def intermediate_invalid_base64(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> [ICA] -> EE
+    ```
+
+    The root and EE are well-formed, but the EE's issuing ICA is supplied as
+    an untrusted intermediate whose PEM body isn't valid base64.
+    """
+
+    root = builder.root_ca()
+    intermediate = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(intermediate)
+    pem = RawPEM("-----BEGIN CERTIFICATE-----\n!!!!not*base64!!!!\n-----END CERTIFICATE-----\n")
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(pem)
+        .peer_certificate(leaf)
+        .fails()
+    )
@@ -0,0 +1,65 @@
+"""
+RFC 7633 (TLS Feature extension, a.k.a. "OCSP Must-Staple") testcases.
+"""
+
+from cryptography import x509
+
+from limbo.assets import ext
+from limbo.testcases._core import Builder, testcase
+
+
+@testcase
+def ee_tls_feature_status_request(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE has a non-critical TLS Feature extension (OID 1.3.6.1.5.5.7.1.24)
+    requiring the `status_request` (5) feature, i.e. "OCSP Must-Staple".
+
+    RFC 7633 4.3 requires clients to enforce the listed features during
+    the TLS handshake, which is outside the scope of path validation.
+    Implementations that don't process this extension can ignore it, since
+    it isn't marked critical.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        extra_extension=ext(x509.TLSFeature([x509.TLSFeatureType.status_request]), critical=False),
+    )
+
+    builder = builder.server_validation().trusted_certs(root).peer_certificate(leaf).succeeds()
+
+
+@testcase
+def ee_tls_feature_status_request_critical(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE has a critical TLS Feature extension (OID 1.3.6.1.5.5.7.1.24)
+    requiring the `status_request` (5) feature. RFC 7633 4.2.2 says that
+    this extension SHOULD NOT be marked critical.
+
+    Path validators that do not process this extension must reject the
+    chain, per RFC 5280 4.2:
+
+    > A certificate-using system MUST reject the certificate if it encounters
+    > a critical extension it does not recognize or a critical extension
+    > that contains information that it cannot process.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        extra_extension=ext(x509.TLSFeature([x509.TLSFeatureType.status_request]), critical=True),
+    )
+
+    builder = builder.server_validation().trusted_certs(root).peer_certificate(leaf).fails()
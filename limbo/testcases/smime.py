@@ -0,0 +1,242 @@
+"""
+S/MIME profile testcases.
+
+These testcases exercise the requirements that RFC 8550 and the CA/Browser
+Forum's S/MIME Baseline Requirements place on S/MIME end-entity certificates.
+"""
+
+from cryptography import x509
+from cryptography.hazmat.primitives.asymmetric import rsa
+from cryptography.hazmat.primitives.asymmetric.types import CertificateIssuerPrivateKeyTypes
+
+from limbo.assets import CertificatePair, ext
+from limbo.models import CertProfile, KnownEKUs, PeerName
+from limbo.testcases._core import Builder, testcase
+
+# The S/MIME BR's reserved policy for mailbox-validated, strict certificates.
+_SMIME_MAILBOX_VALIDATED_STRICT = x509.ObjectIdentifier("2.23.140.1.5.1.3")
+
+_MAILBOX = "alice@example.com"
+
+
+def _ku(
+    *,
+    digital_signature: bool = False,
+    content_commitment: bool = False,
+    key_encipherment: bool = False,
+) -> x509.KeyUsage:
+    return x509.KeyUsage(
+        digital_signature=digital_signature,
+        content_commitment=content_commitment,
+        key_encipherment=key_encipherment,
+        data_encipherment=False,
+        key_agreement=False,
+        key_cert_sign=False,
+        crl_sign=False,
+        encipher_only=False,
+        decipher_only=False,
+    )
+
+
+def _smime_leaf(
+    builder: Builder,
+    parent: CertificatePair,
+    *,
+    eku: x509.ObjectIdentifier | None = x509.ExtendedKeyUsageOID.EMAIL_PROTECTION,
+    san: x509.GeneralName | None = None,
+    key_usage: x509.KeyUsage | None = None,
+    policy: bool = True,
+    key: CertificateIssuerPrivateKeyTypes | None = None,
+) -> CertificatePair:
+    """
+    Produces an S/MIME EE certificate for `alice@example.com`, signed by the
+    given `parent`'s key. The defaults produce a certificate that conforms
+    to the S/MIME BR.
+    """
+
+    if san is None:
+        san = x509.RFC822Name(_MAILBOX)
+
+    if key_usage is None:
+        key_usage = _ku(digital_signature=True)
+
+    return builder.leaf_cert(
+        parent,
+        subject=x509.Name([x509.NameAttribute(x509.NameOID.EMAIL_ADDRESS, _MAILBOX)]),
+        eku=ext(x509.ExtendedKeyUsage([eku]), critical=False) if eku is not None else None,
+        san=ext(x509.SubjectAlternativeName([san]), critical=False),
+        key_usage=ext(key_usage, critical=True),
+        extra_extension=(
+            ext(
+                x509.CertificatePolicies(
+                    [x509.PolicyInformation(_SMIME_MAILBOX_VALIDATED_STRICT, None)]
+                ),
+                critical=False,
+            )
+            if policy
+            else None
+        ),
+        key=key,
+    )
+
+
+def _smime_validation(builder: Builder, root: CertificatePair, leaf: CertificatePair) -> Builder:
+    return (
+        builder.client_validation()
+        .profile(CertProfile.SMIME)
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="RFC822", value=_MAILBOX))
+        .extended_key_usage([KnownEKUs.email_protection])
+    )
+
+
+@testcase
+def ee_mailbox_validated(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is a well-formed mailbox-validated S/MIME certificate: it has
+    an `emailProtection` EKU, an `rfc822Name` SAN matching the expected
+    mailbox, a `digitalSignature` key usage, and the S/MIME BR's reserved
+    policy for strict mailbox-validated certificates (2.23.140.1.5.1.3).
+    """
+
+    root = builder.root_ca()
+    leaf = _smime_leaf(builder, root)
+
+    _smime_validation(builder, root, leaf).succeeds()
+
+
+@testcase
+def ee_rsa_dual_use(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE is an S/MIME certificate with an RSA key and a key usage of
+    `digitalSignature` and `keyEncipherment`, which the S/MIME BR 7.1.2.3
+    permit for "dual use" RSA certificates, i.e. certificates used for both
+    signing and encrypting mail.
+    """
+
+    root = builder.root_ca()
+    leaf = _smime_leaf(
+        builder,
+        root,
+        key=rsa.generate_private_key(public_exponent=65537, key_size=2048),
+        key_usage=_ku(digital_signature=True, key_encipherment=True),
+    )
+
+    _smime_validation(builder, root, leaf).succeeds()
+
+
+@testcase
+def ee_missing_email_protection_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE has an EKU of `clientAuth` rather than `emailProtection`.
+    S/MIME BR 7.1.2.3 requires `emailProtection` in S/MIME certificates,
+    and RFC 8550 4.4.4 requires it for use with S/MIME when the EKU
+    extension is present.
+    """
+
+    root = builder.root_ca()
+    leaf = _smime_leaf(builder, root, eku=x509.ExtendedKeyUsageOID.CLIENT_AUTH)
+
+    _smime_validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_without_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE has no EKU extension. RFC 5280 would treat this as permitting
+    any purpose, but S/MIME BR 7.1.2.3 require the extension to be present
+    and to contain `emailProtection`.
+    """
+
+    root = builder.root_ca()
+    leaf = _smime_leaf(builder, root, eku=None)
+
+    _smime_validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_missing_rfc822_san(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's SAN contains only a `dNSName`, and the expected mailbox appears
+    only in the subject's `emailAddress` attribute. S/MIME BR 7.1.2.3 require
+    every mailbox address to be present as an `rfc822Name` SAN, and RFC 8550
+    3 says that receiving agents MUST recognize and accept mailbox addresses
+    in the SAN.
+    """
+
+    root = builder.root_ca()
+    leaf = _smime_leaf(builder, root, san=x509.DNSName("example.com"))
+
+    _smime_validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_content_commitment_only(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's key usage asserts only `contentCommitment`. S/MIME BR 7.1.2.3
+    permit `contentCommitment` only alongside `digitalSignature`, and a
+    certificate with neither `digitalSignature` nor `keyEncipherment` can
+    neither sign nor encrypt mail.
+    """
+
+    root = builder.root_ca()
+    leaf = _smime_leaf(builder, root, key_usage=_ku(content_commitment=True))
+
+    _smime_validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_missing_smime_policy(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE has no `certificatePolicies` extension. S/MIME BR 7.1.6.4 require
+    every S/MIME subscriber certificate to assert one of the S/MIME BR's
+    reserved policy identifiers.
+    """
+
+    root = builder.root_ca()
+    leaf = _smime_leaf(builder, root, policy=False)
+
+    _smime_validation(builder, root, leaf).fails()
@@ -0,0 +1,149 @@
+"""
+Time-stamping authority (TSA) profile testcases.
+
+These testcases exercise the requirements that RFC 3161 2.3 places on TSA
+certificates:
+
+> The TSA MUST sign each time-stamp message with a key reserved
+> specifically for that purpose. A TSA MAY have distinct private keys,
+> e.g., to accommodate different policies, different algorithms, different
+> private key sizes or to increase the performance. The corresponding
+> certificate MUST contain only one instance of the extended key usage
+> field extension as defined in [RFC2459] Section 4.2.1.13 with
+> KeyPurposeID having value:
+>
+> id-kp-timeStamping. This extension MUST be critical.
+"""
+
+from cryptography import x509
+
+from limbo.assets import CertificatePair, ext
+from limbo.models import CertProfile, KnownEKUs
+from limbo.testcases._core import Builder, testcase
+
+
+def _tsa_leaf(
+    builder: Builder,
+    parent: CertificatePair,
+    ekus: list[x509.ObjectIdentifier] | None,
+    *,
+    critical: bool = True,
+) -> CertificatePair:
+    return builder.leaf_cert(
+        parent,
+        subject=x509.Name.from_rfc4514_string("CN=Example TSA"),
+        eku=ext(x509.ExtendedKeyUsage(ekus), critical=critical) if ekus is not None else None,
+        san=None,
+    )
+
+
+def _tsa_validation(builder: Builder, root: CertificatePair, leaf: CertificatePair) -> Builder:
+    return (
+        builder.client_validation()
+        .profile(CertProfile.TIME_STAMPING)
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(None)
+        .extended_key_usage([KnownEKUs.time_stamping])
+    )
+
+
+@testcase
+def ee_time_stamping_only(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> TSA
+    ```
+
+    The TSA's EKU is critical and contains only `timeStamping`, as
+    required by RFC 3161 2.3.
+    """
+
+    root = builder.root_ca()
+    leaf = _tsa_leaf(builder, root, [x509.ExtendedKeyUsageOID.TIME_STAMPING])
+
+    _tsa_validation(builder, root, leaf).succeeds()
+
+
+@testcase
+def ee_time_stamping_and_server_auth(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> TSA
+    ```
+
+    The TSA's critical EKU contains `serverAuth` alongside `timeStamping`.
+    RFC 3161 2.3 requires `timeStamping` to be the only key purpose, so that
+    the TSA's key is reserved for time-stamping.
+    """
+
+    root = builder.root_ca()
+    leaf = _tsa_leaf(
+        builder,
+        root,
+        [x509.ExtendedKeyUsageOID.TIME_STAMPING, x509.ExtendedKeyUsageOID.SERVER_AUTH],
+    )
+
+    _tsa_validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_missing_time_stamping(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> TSA
+    ```
+
+    The TSA's critical EKU contains `codeSigning` rather than
+    `timeStamping`, so the certificate is not valid for time-stamping.
+    """
+
+    root = builder.root_ca()
+    leaf = _tsa_leaf(builder, root, [x509.ExtendedKeyUsageOID.CODE_SIGNING])
+
+    _tsa_validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_without_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> TSA
+    ```
+
+    The TSA has no EKU extension. RFC 5280 would treat this as permitting
+    any purpose, but RFC 3161 2.3 requires the extension to be present.
+    """
+
+    root = builder.root_ca()
+    leaf = _tsa_leaf(builder, root, None)
+
+    _tsa_validation(builder, root, leaf).fails()
+
+
+@testcase
+def ee_noncritical_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> TSA
+    ```
+
+    The TSA's EKU contains only `timeStamping`, but is not marked critical.
+    This is the inverse of `webpki::eku::ee-critical-eku`: the Web PKI
+    forbids a critical EKU, while RFC 3161 2.3 requires one.
+    """
+
+    root = builder.root_ca()
+    leaf = _tsa_leaf(builder, root, [x509.ExtendedKeyUsageOID.TIME_STAMPING], critical=False)
+
+    _tsa_validation(builder, root, leaf).fails()
@@ -7,7 +7,15 @@ from datetime import datetime
 from cryptography import x509
 from cryptography.hazmat.primitives.asymmetric import dsa, ec, rsa
 
-from limbo.assets import ASSETS_PATH, Certificate, ext
+from limbo.assets import (
+    ASSETS_PATH,
+    TBS_SPKI_INDEX,
+    Certificate,
+    der_tlv,
+    ext,
+    replace_tbs_field,
+    tamper,
+)
 from limbo.models import Feature, KeyUsage, PeerName
 from limbo.testcases._core import Builder, testcase
 
@@ -170,6 +178,80 @@ def forbidden_p192_leaf(builder: Builder) -> None:
     ).fails()
 
 
+def _off_curve_p256_spki() -> bytes:
+    numbers = ec.generate_private_key(ec.SECP256R1()).public_key().public_numbers()
+    # Perturbing y moves the point off the curve, since (x, y) and (x, -y)
+    # are the only points with this x coordinate.
+    x, y = numbers.x.to_bytes(32, "big"), (numbers.y ^ 1).to_bytes(32, "big")
+    # SubjectPublicKeyInfo ::= SEQUENCE { { id-ecPublicKey, prime256v1 }, BIT STRING }
+    return der_tlv(
+        0x30,
+        bytes.fromhex("301306072a8648ce3d020106082a8648ce3d030107")
+        + der_tlv(0x03, b"\x00\x04" + x + y),
+    )
+
+
+@testcase
+def ec_point_not_on_curve_in_intermediate(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA cert conveys a P-256 public key whose point does not satisfy the
+    curve equation, making it cryptographically invalid. RFC 5480 2.2 requires
+    that the `ECPoint` be a point on the named curve.
+
+    No valid signature can be verified with this key, so the EE's signature
+    cannot be verified and the chain is invalid.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+
+    tampered = tamper(ica, root.key, tbs=replace_tbs_field(TBS_SPKI_INDEX, _off_curve_p256_spki()))
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(tampered)
+        .peer_certificate(leaf)
+        .fails()
+    )
+
+
+@testcase
+def ec_point_not_on_curve_in_leaf(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE cert conveys a P-256 public key whose point does not satisfy the
+    curve equation, making it cryptographically invalid. RFC 5480 2.2 requires
+    that the `ECPoint` be a point on the named curve.
+
+    Whether this is detected depends on whether the implementation (or its
+    underlying cryptographic library) fully decodes and checks EE public keys
+    during path validation.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    tampered = tamper(leaf, root.key, tbs=replace_tbs_field(TBS_SPKI_INDEX, _off_curve_p256_spki()))
+
+    builder = builder.server_validation().features([Feature.pedantic_webpki_subscriber_key])
+    builder.trusted_certs(root).peer_certificate(tampered).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).fails()
+
+
 @testcase
 def forbidden_dsa_root(builder: Builder) -> None:
     """
@@ -313,6 +395,76 @@ def forbidden_rsa_key_not_divisable_by_8_in_leaf(builder: Builder) -> None:
     ).fails()
 
 
+@testcase
+def forbidden_rsa_exponent_1_in_leaf(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE cert conveys an RSA-2048 key with a public exponent of 1, which
+    makes "encryption" the identity function and the key trivially
+    breakable. CABF 6.1.6 requires the public exponent to be an odd number
+    greater than or equal to 3.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(root)
+
+    # RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    n = key.public_key().public_numbers().n
+    rsa_public_key = der_tlv(
+        0x30,
+        der_tlv(0x02, n.to_bytes(n.bit_length() // 8 + 1, "big")) + der_tlv(0x02, b"\x01"),
+    )
+    # SubjectPublicKeyInfo ::= SEQUENCE { rsaEncryption, BIT STRING }
+    spki = der_tlv(
+        0x30,
+        bytes.fromhex("300d06092a864886f70d0101010500")
+        + der_tlv(0x03, b"\x00" + rsa_public_key),
+    )
+
+    tampered = tamper(leaf, root.key, tbs=replace_tbs_field(TBS_SPKI_INDEX, spki))
+
+    builder = builder.server_validation().features([Feature.pedantic_webpki_subscriber_key])
+    builder.trusted_certs(root).peer_certificate(tampered).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).fails()
+
+
+@testcase
+def rsa_exponent_3_in_leaf(builder: Builder) -> None:
+    """
+    Produces the following **ambiguous** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE cert conveys an RSA-2048 key with a public exponent of 3. This is
+    permitted by CABF 6.1.6, which only says that the exponent SHOULD be
+    in the range `[2^16 + 1, 2^256 - 1]`, but historically small exponents
+    have enabled attacks against implementations with flawed padding checks.
+
+    Profiles that require `e >= 65537` should reject this chain.
+    """
+
+    root = builder.root_ca()
+
+    leaf_key = rsa.generate_private_key(public_exponent=3, key_size=2048)
+    leaf = builder.leaf_cert(root, key=leaf_key)
+
+    builder = builder.server_validation().features(
+        [Feature.pedantic_webpki_subscriber_key, Feature.strict_key_params]
+    )
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).fails()
+
+
 @testcase
 def v1_cert(builder: Builder) -> None:
     """
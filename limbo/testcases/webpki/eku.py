@@ -41,6 +41,36 @@ def ee_anyeku(builder: Builder) -> None:
     ).extended_key_usage([KnownEKUs.server_auth]).fails()
 
 
+@testcase
+def ee_anyeku_only(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> EE
+    ```
+
+    The EE's extKeyUsage extension contains only `anyExtendedKeyUsage`, but
+    the validator requires `id-kp-serverAuth`. Besides being forbidden under
+    CABF 7.1.2.7.10, `anyExtendedKeyUsage` doesn't indicate the specific
+    purpose that the validator requires.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        eku=ext(
+            x509.ExtendedKeyUsage([x509.ExtendedKeyUsageOID.ANY_EXTENDED_KEY_USAGE]),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder.trusted_certs(root).peer_certificate(leaf).expected_peer_name(
+        PeerName(kind="DNS", value="example.com")
+    ).extended_key_usage([KnownEKUs.server_auth]).fails()
+
+
 @testcase
 def ee_critical_eku(builder: Builder) -> None:
     """
@@ -129,3 +159,100 @@ def root_has_eku(builder: Builder) -> None:
         .peer_certificate(leaf)
         .fails()
     )
+
+
+@testcase
+def ica_server_auth_eku(builder: Builder) -> None:
+    """
+    Produces the following **valid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA's extKeyUsage extension contains `id-kp-serverAuth`, matching
+    both the EE's EKU and the validator's required EKU. CABF 7.1.2.10.6
+    requires TLS subordinate CAs to have an extKeyUsage extension containing
+    `id-kp-serverAuth`.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=ext(x509.ExtendedKeyUsage([x509.OID_SERVER_AUTH]), critical=False),
+    )
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .extended_key_usage([KnownEKUs.server_auth])
+        .succeeds()
+    )
+
+
+@testcase
+def ica_wrong_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The EE's EKU contains `id-kp-serverAuth`, but the ICA's extKeyUsage
+    extension contains only `id-kp-clientAuth`.
+
+    RFC 5280 doesn't define EKUs on CA certificates as constraining their
+    subordinates, but most validators (and CABF 7.1.2.10.6) treat them that
+    way: a CA whose EKU excludes `id-kp-serverAuth` may not issue TLS server
+    certificates.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(
+        root,
+        extra_extension=ext(x509.ExtendedKeyUsage([x509.OID_CLIENT_AUTH]), critical=False),
+    )
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .extended_key_usage([KnownEKUs.server_auth])
+        .fails()
+    )
+
+
+@testcase
+def ica_without_eku(builder: Builder) -> None:
+    """
+    Produces the following **invalid** chain:
+
+    ```
+    root -> ICA -> EE
+    ```
+
+    The ICA has no extKeyUsage extension, which CABF 7.1.2.10.6 requires of
+    TLS subordinate CAs that aren't cross-certified. This is the strict
+    variant of `rfc5280::eku::ica-without-eku`.
+    """
+
+    root = builder.root_ca()
+    ica = builder.intermediate_ca(root)
+    leaf = builder.leaf_cert(ica)
+
+    builder = (
+        builder.conflicts_with("rfc5280::eku::ica-without-eku")
+        .features([Feature.pedantic_webpki_eku])
+        .server_validation()
+        .trusted_certs(root)
+        .untrusted_intermediates(ica)
+        .peer_certificate(leaf)
+        .extended_key_usage([KnownEKUs.server_auth])
+        .fails()
+    )
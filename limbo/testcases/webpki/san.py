@@ -57,6 +57,94 @@ def exact_localhost_ip_san(builder: Builder) -> None:
     ).succeeds()
 
 
+@testcase
+def mixed_dns_ip_san_dns_peer(builder: Builder) -> None:
+    """
+    Produces a chain with a valid EE cert for `example.com`.
+
+    This EE cert contains a Subject Alternative Name with both the dNSName
+    `example.com` and the iPAddress `127.0.0.1`. This should verify
+    successfully against the domain `example.com`: the IP address SAN is
+    simply not considered when matching a DNS reference identifier.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName(
+                [x509.DNSName("example.com"), x509.IPAddress(ipaddress.ip_address("127.0.0.1"))]
+            ),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+    ).succeeds()
+
+
+@testcase
+def mixed_dns_ip_san_ip_peer(builder: Builder) -> None:
+    """
+    Produces a chain with a valid EE cert for IP 127.0.0.1.
+
+    This is the same as `mixed-dns-ip-san-dns-peer`, except that the
+    expected peer is the IP address `127.0.0.1`, which matches the EE's
+    iPAddress SAN.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName(
+                [x509.DNSName("example.com"), x509.IPAddress(ipaddress.ip_address("127.0.0.1"))]
+            ),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="IP", value="127.0.0.1"))
+    ).succeeds()
+
+
+@testcase
+def ip_san_dns_peer(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with only the
+    iPAddress `127.0.0.1`. This should **fail to verify** against a DNS
+    reference identifier with the text `127.0.0.1`: RFC 6125 6.4 only matches
+    DNS reference identifiers against dNSName SANs, and an IP address is not
+    a valid DNS name under RFC 1123 2.1.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName([x509.IPAddress(ipaddress.ip_address("127.0.0.1"))]),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="127.0.0.1"))
+    ).fails()
+
+
 @testcase
 def mismatch_domain_san(builder: Builder) -> None:
     """
@@ -237,6 +325,11 @@ def wildcard_not_in_leftmost_san(builder: Builder) -> None:
     > The client SHOULD NOT attempt to match a presented identifier in
     > which the wildcard character comprises a label other than the
     > left-most label (e.g., do not match bar.*.example.net).
+
+    RFC 9525 6.3 makes this a requirement: the wildcard character must
+    appear "only as the complete content of the left-most label", and a
+    presented identifier that does not meet this "is invalid and MUST be
+    ignored".
     """
     root = builder.root_ca()
     leaf = builder.leaf_cert(
@@ -280,6 +373,31 @@ def wildcard_match_across_labels_san(builder: Builder) -> None:
     ).fails()
 
 
+@testcase
+def wildcard_match_apex_san(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName `*.example.com`.
+    This should **fail to verify** against the domain `example.com`, per RFC 9525 6.3.
+
+    > A wildcard in a presented identifier can only match exactly one label
+    > in a reference identifier.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("*.example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+    ).fails()
+
+
 @testcase
 def wildcard_embedded_ulabel_san(builder: Builder) -> None:
     """
@@ -310,6 +428,133 @@ def wildcard_embedded_ulabel_san(builder: Builder) -> None:
     ).fails()
 
 
+@testcase
+def alabel_san(builder: Builder) -> None:
+    """
+    Produces a chain with a valid EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `xn--mnchen-3ya.de`, i.e. the A-label form of `münchen.de`.
+    This should verify successfully against the domain `xn--mnchen-3ya.de`.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("xn--mnchen-3ya.de")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="xn--mnchen-3ya.de"))
+    ).succeeds()
+
+
+@testcase
+def alabel_wildcard_san(builder: Builder) -> None:
+    """
+    Produces a chain with a valid EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `*.xn--mnchen-3ya.de`, i.e. the A-label form of `*.münchen.de`.
+    This should verify successfully against the domain `xn--bcher-kva.xn--mnchen-3ya.de`,
+    per RFC 6125 6.4.3.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName([x509.DNSName("*.xn--mnchen-3ya.de")]), critical=False
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="xn--bcher-kva.xn--mnchen-3ya.de"))
+    ).succeeds()
+
+
+@testcase
+def ulabel_peer_name_alabel_san(builder: Builder) -> None:
+    """
+    Produces a chain with a valid EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `xn--mnchen-3ya.de`. This should verify successfully against the
+    domain `münchen.de`, per RFC 6125 6.4.2:
+
+    > If the DNS domain name portion of a reference identifier is an
+    > internationalized domain name, then an implementation MUST convert
+    > any U-labels [IDNA-DEFS] in the domain name to A-labels before
+    > checking the domain name.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("xn--mnchen-3ya.de")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="münchen.de"))
+    ).succeeds()
+
+
+@testcase
+def ulabel_peer_name_alabel_wildcard_san(builder: Builder) -> None:
+    """
+    Produces a chain with a valid EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `*.xn--mnchen-3ya.de`. This should verify successfully against the
+    domain `bücher.münchen.de`, per RFC 6125 6.4.2 and 6.4.3: the reference
+    identifier is converted to `xn--bcher-kva.xn--mnchen-3ya.de` before
+    matching, at which point the wildcard matches the left-most label.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName([x509.DNSName("*.xn--mnchen-3ya.de")]), critical=False
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="bücher.münchen.de"))
+    ).succeeds()
+
+
+@testcase
+def ulabel_peer_name_alabel_san_mismatch(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `xn--mnchen-3ya.de`. This should **fail to verify** against the
+    domain `münster.de`, whose A-label form is `xn--mnster-3ya.de`.
+    """
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("xn--mnchen-3ya.de")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="münster.de"))
+    ).fails()
+
+
 @testcase
 def unicode_emoji_san(builder: Builder) -> None:
     """
@@ -463,3 +708,409 @@ def san_wildcard_only_tld(builder: Builder) -> None:
         .peer_certificate(leaf)
         .expected_peer_name(PeerName(kind="DNS", value="com"))
     ).fails()
+
+
+@testcase
+def trailing_dot_san(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `example.com.`, i.e. with a trailing dot for the DNS root label. This
+    should **fail to verify** against the domain `example.com`, since
+    RFC 5280 4.2.1.6 requires dNSNames to be in the "preferred name syntax"
+    of RFC 1034 3.5, which has no trailing dot.
+
+    Implementations that ignore trailing dots when matching names should
+    use `trailing-dot-san-permissive` instead.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com.")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.conflicts_with("webpki::san::trailing-dot-san-permissive")
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+    ).fails()
+
+
+@testcase
+def trailing_dot_san_permissive(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This is the same as `trailing-dot-san`, except that it expects the
+    behavior of implementations that treat a trailing dot as insignificant:
+    `example.com.` and `example.com` name the same domain, so this should
+    verify successfully.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com.")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.conflicts_with("webpki::san::trailing-dot-san")
+        .features([Feature.trailing_dot_matching])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com"))
+    ).succeeds()
+
+
+@testcase
+def trailing_dot_peer_name(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `example.com`. This should **fail to verify** against the domain
+    `example.com.`, since the reference identifier is not in the same
+    "preferred name syntax" as the presented identifier (RFC 6125 6.4.1).
+
+    Implementations that ignore trailing dots when matching names should
+    use `trailing-dot-peer-name-permissive` instead.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.conflicts_with("webpki::san::trailing-dot-peer-name-permissive")
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com."))
+    ).fails()
+
+
+@testcase
+def trailing_dot_peer_name_permissive(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This is the same as `trailing-dot-peer-name`, except that it expects the
+    behavior of implementations that treat a trailing dot as insignificant,
+    e.g. because they accept absolute domain names as reference identifiers.
+    This should verify successfully.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.conflicts_with("webpki::san::trailing-dot-peer-name")
+        .features([Feature.trailing_dot_matching])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="example.com."))
+    ).succeeds()
+
+
+def _underscore_san(builder: Builder, name: str) -> Builder:
+    """
+    Configures a server validation testcase for an EE cert whose only SAN
+    is the dNSName `name`, validated against that same domain.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName(name)]), critical=False),
+    )
+
+    return (
+        builder.server_validation()
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value=name))
+    )
+
+
+@testcase
+def underscore_label_san(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `_.example.com`, whose leftmost label is a single underscore. This
+    should **fail to verify** against the domain `_.example.com`, since
+    RFC 5280 4.2.1.6 requires dNSNames to be in the "preferred name syntax"
+    of RFC 1034 3.5, which only allows letters, digits, and hyphens. The
+    CABF has also forbidden underscores in dNSNames since Ballot SC12.
+    """
+
+    _underscore_san(builder, "_.example.com").conflicts_with(
+        "webpki::san::underscore-label-san-permissive"
+    ).fails()
+
+
+@testcase
+def underscore_label_san_permissive(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This is the same as `underscore-label-san`, except that it expects the
+    behavior of implementations that accept underscores in DNS names, so
+    this should verify successfully.
+    """
+
+    _underscore_san(builder, "_.example.com").conflicts_with(
+        "webpki::san::underscore-label-san"
+    ).features([Feature.allow_underscore_in_dns]).succeeds()
+
+
+@testcase
+def leading_underscore_san(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `_dmarc.example.com`, whose leftmost label begins with an underscore,
+    as is common for service labels (RFC 8552). This should
+    **fail to verify** against the domain `_dmarc.example.com`, for the
+    same reasons as `underscore-label-san`.
+    """
+
+    _underscore_san(builder, "_dmarc.example.com").conflicts_with(
+        "webpki::san::leading-underscore-san-permissive"
+    ).fails()
+
+
+@testcase
+def leading_underscore_san_permissive(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This is the same as `leading-underscore-san`, except that it expects the
+    behavior of implementations that accept underscores in DNS names, so
+    this should verify successfully.
+    """
+
+    _underscore_san(builder, "_dmarc.example.com").conflicts_with(
+        "webpki::san::leading-underscore-san"
+    ).features([Feature.allow_underscore_in_dns]).succeeds()
+
+
+@testcase
+def internal_underscore_san(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `foo_bar.example.com`, whose leftmost label contains an underscore.
+    This should **fail to verify** against the domain
+    `foo_bar.example.com`, for the same reasons as `underscore-label-san`.
+    """
+
+    _underscore_san(builder, "foo_bar.example.com").conflicts_with(
+        "webpki::san::internal-underscore-san-permissive"
+    ).fails()
+
+
+@testcase
+def internal_underscore_san_permissive(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This is the same as `internal-underscore-san`, except that it expects
+    the behavior of implementations that accept underscores in DNS names,
+    so this should verify successfully.
+    """
+
+    _underscore_san(builder, "foo_bar.example.com").conflicts_with(
+        "webpki::san::internal-underscore-san"
+    ).features([Feature.allow_underscore_in_dns]).succeeds()
+
+
+@testcase
+def ip_literal_dns_san_ip_peer(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `192.168.1.1`, i.e. an IP address placed in the wrong SAN type. This
+    should **fail to verify** against the IP address `192.168.1.1`, since
+    RFC 6125 6.4 only matches IP reference identifiers against iPAddress
+    SANs.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("192.168.1.1")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="IP", value="192.168.1.1"))
+    ).fails()
+
+
+@testcase
+def ip_literal_dns_san_dns_peer(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This is the same as `ip-literal-dns-san-ip-peer`, except that the
+    reference identifier is also a DNS name with the text `192.168.1.1`.
+    This should still **fail to verify**, since a dotted-decimal IP address
+    is not a valid DNS name: RFC 1123 2.1 requires that the highest-level
+    label not be all-numeric, precisely so that names can't be confused with
+    IP addresses.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("192.168.1.1")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="192.168.1.1"))
+    ).fails()
+
+
+@testcase
+def exact_private_ip_san(builder: Builder) -> None:
+    """
+    Produces a chain with a valid EE cert for IP 192.168.1.1.
+
+    This is the correctly-encoded counterpart to the `ip-literal-dns-san-*`
+    testcases: the EE cert contains a Subject Alternative Name with the
+    iPAddress `192.168.1.1`, which should verify successfully against that
+    same IP address.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(
+            x509.SubjectAlternativeName([x509.IPAddress(ipaddress.ip_address("192.168.1.1"))]),
+            critical=False,
+        ),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="IP", value="192.168.1.1"))
+    ).succeeds()
+
+
+@testcase
+def double_wildcard_san(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `*.*.example.com`. This should **fail to verify** against the domain
+    `foo.bar.example.com`, per RFC 9525 6.3, which only allows a presented
+    identifier to contain a single wildcard character, as the complete
+    content of the left-most label:
+
+    > 1. There is only one wildcard character.
+    >
+    > 2. The wildcard character appears only as the complete content of the
+    >    left-most label.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("*.*.example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="foo.bar.example.com"))
+    ).fails()
+
+
+@testcase
+def wildcard_prefix_label_san(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This EE cert contains a Subject Alternative Name with the dNSName
+    `*foo.example.com`, whose wildcard is only part of the left-most label.
+    This should **fail to verify** against the domain `barfoo.example.com`,
+    per RFC 9525 6.3, which requires the wildcard character to be the
+    complete content of the left-most label.
+
+    Implementations that follow RFC 6125 6.4.3, which allowed clients to
+    match such wildcards, should use `wildcard-prefix-label-san-permissive`
+    instead.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("*foo.example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.conflicts_with("webpki::san::wildcard-prefix-label-san-permissive")
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="barfoo.example.com"))
+    ).fails()
+
+
+@testcase
+def wildcard_prefix_label_san_permissive(builder: Builder) -> None:
+    """
+    Produces a chain with an EE cert.
+
+    This is the same as `wildcard-prefix-label-san`, except that it expects
+    the behavior permitted by RFC 6125 6.4.3, which RFC 9525 6.3 has since
+    forbidden:
+
+    > The client MAY match a presented identifier in which the wildcard
+    > character is not the only character of the label (e.g.,
+    > baz*.example.net and *baz.example.net and b*z.example.net would be
+    > taken to match baz1.example.net and foobaz.example.net and
+    > buzz.example.net, respectively).
+
+    This should verify successfully.
+    """
+
+    root = builder.root_ca()
+    leaf = builder.leaf_cert(
+        root,
+        san=ext(x509.SubjectAlternativeName([x509.DNSName("*foo.example.com")]), critical=False),
+    )
+
+    builder = builder.server_validation()
+    builder = (
+        builder.conflicts_with("webpki::san::wildcard-prefix-label-san")
+        .features([Feature.partial_wildcard_matching])
+        .trusted_certs(root)
+        .peer_certificate(leaf)
+        .expected_peer_name(PeerName(kind="DNS", value="barfoo.example.com"))
+    ).succeeds()
@@ -0,0 +1,442 @@
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path, process::exit};
+
+use limbo_harness_support::{
+    load_limbo_from_path,
+    models::{ActualResult, ExpectedResult, Limbo, LimboResult},
+};
+
+const USAGE: &str = "usage: limbo-compare [--html] [--limbo LIMBO_JSON] RESULT_JSON...";
+
+enum Format {
+    Markdown,
+    Html,
+}
+
+/// A single harness's outcome for a testcase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    Correct,
+    Incorrect,
+    Skipped,
+    Missing,
+}
+
+impl Outcome {
+    fn symbol(self) -> &'static str {
+        match self {
+            Outcome::Correct => "✅",
+            Outcome::Incorrect => "❌",
+            Outcome::Skipped => "⏭",
+            Outcome::Missing => "",
+        }
+    }
+}
+
+struct Row<'a> {
+    id: &'a str,
+    expected: &'a ExpectedResult,
+    outcomes: Vec<Outcome>,
+    disagreement: bool,
+}
+
+struct Score<'a> {
+    harness: &'a str,
+    correct: usize,
+    evaluated: usize,
+    skipped: usize,
+    total: usize,
+}
+
+fn main() {
+    let mut format = Format::Markdown;
+    let mut limbo_path = String::from("limbo.json");
+    let mut result_paths = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--html" => format = Format::Html,
+            "--limbo" => match args.next() {
+                Some(path) => limbo_path = path,
+                None => usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ => result_paths.push(arg),
+        }
+    }
+
+    if result_paths.is_empty() {
+        usage();
+    }
+
+    let limbo = load_limbo_from_path(Path::new(&limbo_path));
+    let results = result_paths
+        .iter()
+        .map(|path| load_result(Path::new(path)))
+        .collect::<Vec<_>>();
+
+    let rows = join(&limbo, &results);
+    let scores = score(&results, &rows);
+
+    let output = match format {
+        Format::Markdown => render_markdown(&results, &rows, &scores),
+        Format::Html => render_html(&results, &rows, &scores),
+    };
+    print!("{output}");
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    exit(2);
+}
+
+fn load_result(path: &Path) -> LimboResult {
+    let file = File::open(path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    serde_json::from_reader(BufReader::new(file))
+        .unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+/// Joins each harness's results against the testcases in `limbo`, by ID.
+fn join<'a>(limbo: &'a Limbo, results: &[LimboResult]) -> Vec<Row<'a>> {
+    let by_id = results
+        .iter()
+        .map(|result| {
+            result
+                .results
+                .iter()
+                .map(|r| (r.id.as_str(), r.actual_result))
+                .collect::<HashMap<_, _>>()
+        })
+        .collect::<Vec<_>>();
+
+    limbo
+        .testcases
+        .iter()
+        .map(|tc| {
+            let id = tc.id.as_str();
+            let actuals = by_id
+                .iter()
+                .map(|results| results.get(id).copied())
+                .collect::<Vec<_>>();
+
+            let outcomes = actuals
+                .iter()
+                .map(|actual| match actual {
+                    Some(ActualResult::Skipped) => Outcome::Skipped,
+                    Some(actual) if actual.matches(&tc.expected_result) => Outcome::Correct,
+                    Some(_) => Outcome::Incorrect,
+                    None => Outcome::Missing,
+                })
+                .collect::<Vec<_>>();
+
+            // Harnesses disagree when at least two of them evaluated the
+            // testcase and came to different results.
+            let mut evaluated = actuals
+                .iter()
+                .flatten()
+                .filter(|actual| **actual != ActualResult::Skipped);
+            let disagreement = match evaluated.next() {
+                Some(first) => evaluated.any(|actual| actual != first),
+                None => false,
+            };
+
+            Row {
+                id,
+                expected: &tc.expected_result,
+                outcomes,
+                disagreement,
+            }
+        })
+        .collect()
+}
+
+/// Computes each harness's conformance score: the number of correct results
+/// out of all testcases, so that skipped (and missing) testcases count
+/// against it.
+fn score<'a>(results: &'a [LimboResult], rows: &[Row]) -> Vec<Score<'a>> {
+    results
+        .iter()
+        .enumerate()
+        .map(|(idx, result)| {
+            let outcomes = rows.iter().map(|row| row.outcomes[idx]);
+            let correct = outcomes.clone().filter(|o| *o == Outcome::Correct).count();
+            let incorrect = outcomes
+                .clone()
+                .filter(|o| *o == Outcome::Incorrect)
+                .count();
+            let skipped = outcomes.filter(|o| *o == Outcome::Skipped).count();
+
+            Score {
+                harness: &result.harness,
+                correct,
+                evaluated: correct + incorrect,
+                skipped,
+                total: rows.len(),
+            }
+        })
+        .collect()
+}
+
+fn percent(score: &Score) -> f64 {
+    if score.total == 0 {
+        return 0.0;
+    }
+    100.0 * score.correct as f64 / score.total as f64
+}
+
+fn render_markdown(results: &[LimboResult], rows: &[Row], scores: &[Score]) -> String {
+    let mut out = String::new();
+
+    out.push_str("## Conformance\n\n");
+    out.push_str("| Harness | Correct | Evaluated | Skipped | Total | Score |\n");
+    out.push_str("|---|---|---|---|---|---|\n");
+    for score in scores {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {:.2}% |\n",
+            score.harness,
+            score.correct,
+            score.evaluated,
+            score.skipped,
+            score.total,
+            percent(score)
+        ));
+    }
+
+    out.push_str("\n## Results\n\n");
+    out.push_str("| Testcase | Expected |");
+    for result in results {
+        out.push_str(&format!(" {} |", result.harness));
+    }
+    out.push_str("\n|---|---|");
+    out.push_str(&"---|".repeat(results.len()));
+    out.push('\n');
+
+    for row in rows {
+        // Markdown has no cell styling, so disagreements are flagged in bold.
+        let id = if row.disagreement {
+            format!("**`{}`** ⚠️", row.id)
+        } else {
+            format!("`{}`", row.id)
+        };
+        out.push_str(&format!("| {id} | {} |", row.expected.to_string()));
+        for outcome in &row.outcomes {
+            out.push_str(&format!(" {} |", outcome.symbol()));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html(results: &[LimboResult], rows: &[Row], scores: &[Score]) -> String {
+    let mut out = String::new();
+
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Limbo interop matrix</title>\n");
+    out.push_str("<style>\n");
+    out.push_str("table { border-collapse: collapse; }\n");
+    out.push_str("th, td { border: 1px solid #ccc; padding: 2px 6px; }\n");
+    out.push_str("tr.disagreement td { background-color: #fff3cd; }\n");
+    out.push_str("</style>\n</head>\n<body>\n");
+
+    out.push_str("<h2>Conformance</h2>\n<table>\n");
+    out.push_str(
+        "<tr><th>Harness</th><th>Correct</th><th>Evaluated</th>\
+         <th>Skipped</th><th>Total</th><th>Score</th></tr>\n",
+    );
+    for score in scores {
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.2}%</td></tr>\n",
+            html_escape(score.harness),
+            score.correct,
+            score.evaluated,
+            score.skipped,
+            score.total,
+            percent(score)
+        ));
+    }
+    out.push_str("</table>\n");
+
+    out.push_str("<h2>Results</h2>\n<table>\n<tr><th>Testcase</th><th>Expected</th>");
+    for result in results {
+        out.push_str(&format!("<th>{}</th>", html_escape(&result.harness)));
+    }
+    out.push_str("</tr>\n");
+
+    for row in rows {
+        if row.disagreement {
+            out.push_str("<tr class=\"disagreement\">");
+        } else {
+            out.push_str("<tr>");
+        }
+        out.push_str(&format!(
+            "<td><code>{}</code></td><td>{}</td>",
+            html_escape(row.id),
+            row.expected.to_string()
+        ));
+        for outcome in &row.outcomes {
+            out.push_str(&format!("<td>{}</td>", outcome.symbol()));
+        }
+        out.push_str("</tr>\n");
+    }
+
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use limbo_harness_support::models::{ResultContextCode, SkipReason, Testcase, TestcaseResult};
+
+    use super::*;
+
+    /// Returns a suite of copies of the support crate's fixture testcase,
+    /// one per `(id, expected)` pair.
+    fn suite(testcases: &[(&str, ExpectedResult)]) -> Limbo {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../harness-support/rust/tests/fixtures/limbo.json");
+        let mut limbo = load_limbo_from_path(&path);
+        let testcase = limbo.testcases.pop().unwrap();
+        limbo.testcases = testcases
+            .iter()
+            .map(|(id, expected)| Testcase {
+                id: id.parse().unwrap(),
+                expected_result: *expected,
+                ..testcase.clone()
+            })
+            .collect();
+        limbo
+    }
+
+    /// Returns `harness`'s results for `limbo`, with one entry per
+    /// `(id, actual)` pair.
+    fn results(limbo: &Limbo, harness: &str, actuals: &[(&str, ActualResult)]) -> LimboResult {
+        let results = actuals
+            .iter()
+            .map(|(id, actual)| {
+                let tc = limbo
+                    .testcases
+                    .iter()
+                    .find(|tc| tc.id.as_str() == *id)
+                    .unwrap();
+                match actual {
+                    ActualResult::Success => TestcaseResult::success(tc),
+                    ActualResult::Failure => {
+                        TestcaseResult::fail(tc, ResultContextCode::PathBuildingFailed, "stub")
+                    }
+                    ActualResult::Skipped => TestcaseResult::skip(
+                        tc,
+                        ResultContextCode::NotEvaluated,
+                        SkipReason::NotEvaluated,
+                    ),
+                }
+            })
+            .collect();
+
+        LimboResult {
+            version: 1,
+            harness: harness.into(),
+            suite_version: limbo.suite_version(),
+            results,
+            metadata: None,
+        }
+    }
+
+    fn fixture() -> (Limbo, Vec<LimboResult>) {
+        let limbo = suite(&[
+            ("stub::agree", ExpectedResult::Success),
+            ("stub::disagree", ExpectedResult::Failure),
+            ("stub::skipped", ExpectedResult::Success),
+            ("stub::missing", ExpectedResult::Success),
+        ]);
+        let results = vec![
+            results(
+                &limbo,
+                "a",
+                &[
+                    ("stub::agree", ActualResult::Success),
+                    ("stub::disagree", ActualResult::Failure),
+                    ("stub::skipped", ActualResult::Skipped),
+                    ("stub::missing", ActualResult::Success),
+                ],
+            ),
+            results(
+                &limbo,
+                "b",
+                &[
+                    ("stub::agree", ActualResult::Success),
+                    ("stub::disagree", ActualResult::Success),
+                    ("stub::skipped", ActualResult::Failure),
+                ],
+            ),
+        ];
+        (limbo, results)
+    }
+
+    #[test]
+    fn join_outcomes() {
+        let (limbo, results) = fixture();
+        let rows = join(&limbo, &results);
+
+        let outcomes = rows
+            .iter()
+            .map(|row| (row.id, row.outcomes.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            [
+                ("stub::agree", vec![Outcome::Correct, Outcome::Correct]),
+                ("stub::disagree", vec![Outcome::Correct, Outcome::Incorrect]),
+                ("stub::skipped", vec![Outcome::Skipped, Outcome::Incorrect]),
+                ("stub::missing", vec![Outcome::Correct, Outcome::Missing]),
+            ]
+        );
+    }
+
+    #[test]
+    fn join_disagreements() {
+        let (limbo, results) = fixture();
+        let rows = join(&limbo, &results);
+
+        // Skipped and missing results can't disagree with anything.
+        let disagreements = rows
+            .iter()
+            .filter(|row| row.disagreement)
+            .map(|row| row.id)
+            .collect::<Vec<_>>();
+        assert_eq!(disagreements, ["stub::disagree"]);
+
+        let markdown = render_markdown(&results, &rows, &score(&results, &rows));
+        assert!(markdown.contains("| **`stub::disagree`** ⚠️ |"));
+        assert!(markdown.contains("| `stub::agree` |"));
+
+        let html = render_html(&results, &rows, &score(&results, &rows));
+        assert!(html.contains("<tr class=\"disagreement\"><td><code>stub::disagree</code>"));
+        assert!(html.contains("<tr><td><code>stub::agree</code>"));
+    }
+
+    #[test]
+    fn score_counts_skipped_and_missing_against_the_harness() {
+        let (limbo, results) = fixture();
+        let rows = join(&limbo, &results);
+        let scores = score(&results, &rows);
+
+        let counts = scores
+            .iter()
+            .map(|s| (s.harness, s.correct, s.evaluated, s.skipped, s.total))
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [("a", 3, 3, 1, 4), ("b", 1, 3, 0, 4)]);
+        assert_eq!(percent(&scores[0]), 75.0);
+        assert_eq!(percent(&scores[1]), 25.0);
+    }
+}
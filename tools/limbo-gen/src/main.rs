@@ -0,0 +1,362 @@
+use std::{collections::HashMap, fs, net::IpAddr, path::PathBuf, process::exit};
+
+use limbo_harness_support::models::Testcase;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose, SanType,
+};
+use serde::Deserialize;
+use serde_json::json;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+const USAGE: &str = "usage: limbo-gen SPEC_TOML [--pem-dir DIR]";
+
+// The default validity period, matching the Python testcase builder.
+const DEFAULT_NOT_BEFORE: &str = "1970-01-01T00:00:01Z";
+const DEFAULT_NOT_AFTER: &str = "2969-05-03T00:00:01Z";
+
+/// A declarative description of a single testcase.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Spec {
+    id: String,
+    description: String,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default = "default_validation_kind")]
+    validation_kind: String,
+    expected_result: String,
+    expected_peer_name: Option<PeerNameSpec>,
+    validation_time: Option<String>,
+    /// The key usages, extended key usages and signature algorithms that
+    /// the validator should require; passed through to the testcase as is.
+    #[serde(default)]
+    key_usage: Vec<String>,
+    #[serde(default)]
+    extended_key_usage: Vec<String>,
+    #[serde(default)]
+    signature_algorithms: Vec<String>,
+    certs: Vec<CertSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PeerNameSpec {
+    kind: String,
+    value: String,
+}
+
+/// A single certificate in the chain. Certificates are generated in the
+/// order they appear, so issuers must be listed before their subjects.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CertSpec {
+    name: String,
+    role: Role,
+    subject: String,
+    /// The `name` of the issuing certificate, or `None` if self-signed.
+    issuer: Option<String>,
+    #[serde(default)]
+    key: KeyType,
+    #[serde(default)]
+    ca: bool,
+    pathlen: Option<u8>,
+    #[serde(default)]
+    san: Vec<String>,
+    /// Overrides the default key usage: `keyCertSign` for CAs, and
+    /// `digitalSignature` otherwise.
+    ku: Option<Vec<String>>,
+    #[serde(default)]
+    eku: Vec<String>,
+    not_before: Option<String>,
+    not_after: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Role {
+    Trusted,
+    Intermediate,
+    Peer,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum KeyType {
+    #[default]
+    P256,
+    P384,
+    Ed25519,
+}
+
+impl KeyType {
+    fn generate(&self) -> KeyPair {
+        let alg = match self {
+            KeyType::P256 => &rcgen::PKCS_ECDSA_P256_SHA256,
+            KeyType::P384 => &rcgen::PKCS_ECDSA_P384_SHA384,
+            KeyType::Ed25519 => &rcgen::PKCS_ED25519,
+        };
+        KeyPair::generate_for(alg).unwrap()
+    }
+}
+
+fn default_validation_kind() -> String {
+    "SERVER".into()
+}
+
+fn main() {
+    let mut spec_path = None;
+    let mut pem_dir = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--pem-dir" => match args.next() {
+                Some(dir) => pem_dir = Some(PathBuf::from(dir)),
+                None => usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ if spec_path.is_none() => spec_path = Some(PathBuf::from(arg)),
+            _ => usage(),
+        }
+    }
+
+    let Some(spec_path) = spec_path else { usage() };
+    let contents = fs::read_to_string(&spec_path)
+        .unwrap_or_else(|e| fail(&format!("{}: {e}", spec_path.display())));
+    let spec: Spec = match toml::from_str(&contents) {
+        Ok(spec) => spec,
+        Err(e) => fail(&format!("{}: {e}", spec_path.display())),
+    };
+
+    let certs = generate(&spec.certs);
+
+    if let Some(pem_dir) = pem_dir {
+        fs::create_dir_all(&pem_dir)
+            .unwrap_or_else(|e| fail(&format!("{}: {e}", pem_dir.display())));
+        for (cert_spec, (cert, _)) in spec.certs.iter().zip(&certs) {
+            let path = pem_dir.join(format!("{}.pem", cert_spec.name));
+            fs::write(&path, cert.pem())
+                .unwrap_or_else(|e| fail(&format!("{}: {e}", path.display())));
+        }
+    }
+
+    let testcase = render(&spec, &certs);
+    serde_json::to_writer_pretty(std::io::stdout(), &testcase).unwrap();
+    println!();
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    exit(2);
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("limbo-gen: {msg}");
+    exit(1);
+}
+
+/// Generates each certificate in `specs`, in order.
+fn generate(specs: &[CertSpec]) -> Vec<(Certificate, KeyPair)> {
+    let mut certs: Vec<(Certificate, KeyPair)> = vec![];
+    let mut by_name = HashMap::new();
+
+    for spec in specs {
+        let key = spec.key.generate();
+        let params = params(spec);
+
+        let cert = match &spec.issuer {
+            None => params.self_signed(&key),
+            Some(issuer) => {
+                let Some(&idx) = by_name.get(issuer.as_str()) else {
+                    fail(&format!("{}: unknown issuer {issuer}", spec.name))
+                };
+                let (issuer_cert, issuer_key) = &certs[idx];
+                params.signed_by(&key, issuer_cert, issuer_key)
+            }
+        }
+        .unwrap_or_else(|e| fail(&format!("{}: {e}", spec.name)));
+
+        if by_name.insert(spec.name.as_str(), certs.len()).is_some() {
+            fail(&format!("duplicate certificate name: {}", spec.name));
+        }
+        certs.push((cert, key));
+    }
+
+    certs
+}
+
+fn params(spec: &CertSpec) -> CertificateParams {
+    let mut params = CertificateParams::default();
+    params.distinguished_name = distinguished_name(&spec.subject);
+    params.not_before = datetime(spec.not_before.as_deref().unwrap_or(DEFAULT_NOT_BEFORE));
+    params.not_after = datetime(spec.not_after.as_deref().unwrap_or(DEFAULT_NOT_AFTER));
+    params.use_authority_key_identifier_extension = spec.issuer.is_some();
+
+    if spec.ca {
+        params.is_ca = IsCa::Ca(match spec.pathlen {
+            Some(pathlen) => BasicConstraints::Constrained(pathlen),
+            None => BasicConstraints::Unconstrained,
+        });
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign];
+    } else {
+        params.is_ca = IsCa::ExplicitNoCa;
+        params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+    }
+
+    if let Some(ku) = &spec.ku {
+        params.key_usages = ku
+            .iter()
+            .map(|ku| match ku.as_str() {
+                "digitalSignature" => KeyUsagePurpose::DigitalSignature,
+                "contentCommitment" => KeyUsagePurpose::ContentCommitment,
+                "keyEncipherment" => KeyUsagePurpose::KeyEncipherment,
+                "dataEncipherment" => KeyUsagePurpose::DataEncipherment,
+                "keyAgreement" => KeyUsagePurpose::KeyAgreement,
+                "keyCertSign" => KeyUsagePurpose::KeyCertSign,
+                "cRLSign" => KeyUsagePurpose::CrlSign,
+                "encipherOnly" => KeyUsagePurpose::EncipherOnly,
+                "decipherOnly" => KeyUsagePurpose::DecipherOnly,
+                _ => fail(&format!("{}: unsupported key usage {ku}", spec.name)),
+            })
+            .collect();
+    }
+
+    params.subject_alt_names = spec
+        .san
+        .iter()
+        .map(|san| match san.parse::<IpAddr>() {
+            Ok(ip) => SanType::IpAddress(ip),
+            Err(_) => match san.clone().try_into() {
+                Ok(name) => SanType::DnsName(name),
+                Err(e) => fail(&format!("{}: invalid SAN {san}: {e}", spec.name)),
+            },
+        })
+        .collect();
+
+    params.extended_key_usages = spec
+        .eku
+        .iter()
+        .map(|eku| match eku.as_str() {
+            "serverAuth" => ExtendedKeyUsagePurpose::ServerAuth,
+            "clientAuth" => ExtendedKeyUsagePurpose::ClientAuth,
+            "codeSigning" => ExtendedKeyUsagePurpose::CodeSigning,
+            "emailProtection" => ExtendedKeyUsagePurpose::EmailProtection,
+            "timeStamping" => ExtendedKeyUsagePurpose::TimeStamping,
+            "OCSPSigning" => ExtendedKeyUsagePurpose::OcspSigning,
+            _ => fail(&format!("{}: unsupported EKU {eku}", spec.name)),
+        })
+        .collect();
+
+    params
+}
+
+/// Parses a simple RFC 4514-style name, e.g. `CN=example,O=Example`.
+fn distinguished_name(name: &str) -> DistinguishedName {
+    let mut dn = DistinguishedName::new();
+    for attr in name.split(',').filter(|attr| !attr.is_empty()) {
+        let Some((ty, value)) = attr.split_once('=') else {
+            fail(&format!("malformed name attribute: {attr}"))
+        };
+        let ty = match ty.trim() {
+            "C" => DnType::CountryName,
+            "L" => DnType::LocalityName,
+            "ST" => DnType::StateOrProvinceName,
+            "O" => DnType::OrganizationName,
+            "OU" => DnType::OrganizationalUnitName,
+            "CN" => DnType::CommonName,
+            ty => fail(&format!("unsupported name attribute type: {ty}")),
+        };
+        dn.push(ty, value.trim());
+    }
+    dn
+}
+
+fn datetime(value: &str) -> OffsetDateTime {
+    OffsetDateTime::parse(value, &Rfc3339)
+        .unwrap_or_else(|e| fail(&format!("invalid RFC 3339 time {value}: {e}")))
+}
+
+/// Renders a `Testcase` from `spec` and its generated certificates.
+///
+/// The testcase is round-tripped through the `Testcase` model, so that any
+/// mistakes in the spec (e.g. an unknown feature) are caught here.
+fn render(spec: &Spec, certs: &[(Certificate, KeyPair)]) -> Testcase {
+    let pems_for = |role: Role| {
+        spec.certs
+            .iter()
+            .zip(certs)
+            .filter(|(cert_spec, _)| cert_spec.role == role)
+            .map(|(_, (cert, _))| cert.pem())
+            .collect::<Vec<_>>()
+    };
+
+    let mut peers = spec
+        .certs
+        .iter()
+        .zip(certs)
+        .filter(|(cert_spec, _)| cert_spec.role == Role::Peer);
+    let (Some((_, (peer_cert, peer_key))), None) = (peers.next(), peers.next()) else {
+        fail("exactly one certificate must have the peer role")
+    };
+
+    let expected_peer_name = spec
+        .expected_peer_name
+        .as_ref()
+        .map(|peer| json!({ "kind": peer.kind, "value": peer.value }));
+
+    let testcase = json!({
+        "id": spec.id,
+        "features": spec.features,
+        "description": spec.description,
+        "validation_kind": spec.validation_kind,
+        "trusted_certs": pems_for(Role::Trusted),
+        "untrusted_intermediates": pems_for(Role::Intermediate),
+        "peer_certificate": peer_cert.pem(),
+        "peer_certificate_key": peer_key.serialize_pem(),
+        "validation_time": spec.validation_time,
+        "signature_algorithms": spec.signature_algorithms,
+        "key_usage": spec.key_usage,
+        "extended_key_usage": spec.extended_key_usage,
+        "expected_result": spec.expected_result,
+        "expected_peer_name": expected_peer_name,
+        "expected_peer_names": [],
+    });
+
+    serde_json::from_value(testcase).unwrap_or_else(|e| fail(&format!("invalid testcase: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use limbo_harness_support::{
+        load_limbo_from_str,
+        models::{ExpectedResult, KeyUsage, KnownEkUs},
+    };
+
+    use super::*;
+
+    #[test]
+    fn render_example_spec() {
+        let spec: Spec = toml::from_str(include_str!("../example.toml")).unwrap();
+        let certs = generate(&spec.certs);
+        let testcase = render(&spec, &certs);
+
+        // The rendered testcase must be loadable as part of a suite.
+        let json = json!({ "version": 1, "testcases": [testcase] }).to_string();
+        let limbo = load_limbo_from_str(&json).unwrap();
+        let [testcase] = limbo.testcases.as_slice() else {
+            panic!("expected exactly one testcase");
+        };
+
+        assert_eq!(testcase.id.as_str(), "example::root-intermediate-ee");
+        assert_eq!(testcase.expected_result, ExpectedResult::Success);
+        assert_eq!(testcase.trusted_certs, [certs[0].0.pem()]);
+        assert_eq!(testcase.untrusted_intermediates, [certs[1].0.pem()]);
+        assert_eq!(testcase.peer_certificate, certs[2].0.pem());
+        assert_eq!(testcase.key_usage, [KeyUsage::DigitalSignature]);
+        assert_eq!(testcase.extended_key_usage, [KnownEkUs::ServerAuth]);
+    }
+}
@@ -0,0 +1,162 @@
+use std::{fs, process::exit};
+
+use limbo_harness_support::models::{Limbo, LimboResult};
+use serde::de::DeserializeOwned;
+
+const USAGE: &str = "usage: limbo-validate-schema [--kind limbo|result] FILE...";
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    /// A test suite, i.e. `limbo.json`.
+    Limbo,
+    /// A harness's results.
+    Result,
+}
+
+fn main() {
+    let mut kind = None;
+    let mut paths = vec![];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--kind" => match args.next().as_deref() {
+                Some("limbo") => kind = Some(Kind::Limbo),
+                Some("result") => kind = Some(Kind::Result),
+                _ => usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ => paths.push(arg),
+        }
+    }
+
+    if paths.is_empty() {
+        usage();
+    }
+
+    let mut failed = false;
+    for path in &paths {
+        match validate(path, kind) {
+            Ok(summary) => println!("{path}: ok ({summary})"),
+            Err(e) => {
+                println!("{path}: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    exit(2);
+}
+
+/// Validates the JSON file at `path`, returning a short summary of its
+/// contents on success.
+///
+/// If `kind` isn't given, it's inferred from the file's top-level keys.
+fn validate(path: &str, kind: Option<Kind>) -> Result<String, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("read failed: {e}"))?;
+
+    let kind = match kind {
+        Some(kind) => kind,
+        None => detect(&contents)?,
+    };
+
+    match kind {
+        Kind::Limbo => {
+            let limbo: Limbo = deserialize(&contents)?;
+            Ok(format!("{} testcases", limbo.testcases.len()))
+        }
+        Kind::Result => {
+            let result: LimboResult = deserialize(&contents)?;
+            Ok(format!(
+                "{} results from {}",
+                result.results.len(),
+                result.harness
+            ))
+        }
+    }
+}
+
+fn detect(contents: &str) -> Result<Kind, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(contents).map_err(|e| format!("invalid JSON: {e}"))?;
+
+    match value.as_object() {
+        Some(obj) if obj.contains_key("testcases") => Ok(Kind::Limbo),
+        Some(obj) if obj.contains_key("results") => Ok(Kind::Result),
+        _ => Err("can't infer file kind (expected `testcases` or `results`); use --kind".into()),
+    }
+}
+
+/// Deserializes `contents`, reporting the JSON path of the first error.
+fn deserialize<T: DeserializeOwned>(contents: &str) -> Result<T, String> {
+    let de = &mut serde_json::Deserializer::from_str(contents);
+    serde_path_to_error::deserialize(de)
+        .map_err(|e| format!("error at {}: {}", e.path(), e.inner()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn fixture(path: &str) -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(path)
+            .to_str()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn detect_kind() {
+        assert!(matches!(
+            detect(r#"{"version": 1, "testcases": []}"#),
+            Ok(Kind::Limbo)
+        ));
+        assert!(matches!(
+            detect(r#"{"version": 1, "harness": "h", "results": []}"#),
+            Ok(Kind::Result)
+        ));
+        assert!(detect(r#"{"version": 1}"#)
+            .unwrap_err()
+            .starts_with("can't infer file kind"));
+        assert!(detect("[]").is_err());
+    }
+
+    #[test]
+    fn validate_limbo_fixture() {
+        let path = fixture("../../harness-support/rust/tests/fixtures/limbo.json");
+
+        assert_eq!(validate(&path, None).unwrap(), "1 testcases");
+        assert_eq!(validate(&path, Some(Kind::Limbo)).unwrap(), "1 testcases");
+    }
+
+    #[test]
+    fn validate_invalid_fixture() {
+        let path = fixture("tests/fixtures/invalid-expected-result.json");
+        let err = validate(&path, None).unwrap_err();
+
+        assert!(
+            err.starts_with("error at testcases[0].expected_result: "),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn validate_missing_file() {
+        let err = validate(&fixture("tests/fixtures/does-not-exist.json"), None).unwrap_err();
+
+        assert!(err.starts_with("read failed: "), "{err}");
+    }
+}